@@ -0,0 +1,41 @@
+//! Compares loading the same prefixed str bytes with and without the UTF-8 check.
+//!
+//! Run with `cargo bench --bench prefix_str`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use stevia::types::U32PrefixStr;
+
+const ITERATIONS: u32 = 100_000;
+
+/// Returns the average time of running `f` once.
+fn measure(f: impl Fn() -> usize) -> Duration {
+    // warm up
+    for _ in 0..ITERATIONS / 10 {
+        black_box(f());
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let value = "stevia ".repeat(512);
+    let mut bytes = (value.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(value.as_bytes());
+
+    let checked = measure(|| U32PrefixStr::from_bytes(black_box(&bytes)).unwrap().len());
+
+    let validated = U32PrefixStr::validate(&bytes).unwrap();
+    let trusted = measure(|| U32PrefixStr::from_bytes_trusted(black_box(validated)).len());
+
+    println!("str length: {} bytes", value.len());
+    println!("from_bytes:         {checked:?}");
+    println!("from_bytes_trusted: {trusted:?}");
+}
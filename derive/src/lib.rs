@@ -0,0 +1,171 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields};
+
+/// Derives the `Nullable` trait for a newtype wrapper around a `Nullable` type.
+///
+/// The wrapper must be a struct with a single field; the implementation forwards
+/// `is_some` and `is_none` to the field, so the wrapper is `None` when its field is.
+///
+/// ```
+/// use bytemuck::{Pod, Zeroable};
+/// use stevia::pod::{Nullable, PodOption, Sentinel};
+///
+/// #[repr(transparent)]
+/// #[derive(Clone, Copy, Nullable, Pod, Zeroable)]
+/// struct Key(Sentinel<[u8; 32], 0>);
+///
+/// let option = PodOption::new(Key(Sentinel::none()));
+/// assert!(option.value().is_none());
+///
+/// let option = PodOption::new(Key(Sentinel::new([1; 32])));
+/// assert!(option.value().is_some());
+/// ```
+///
+/// Enums and structs with more than one field are rejected with a compile error.
+#[proc_macro_derive(Nullable)]
+pub fn derive_nullable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match nullable_impl(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn nullable_impl(input: &DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "Nullable can only be derived for structs",
+            ))
+        }
+    };
+
+    let field = match fields {
+        Fields::Named(named) if named.named.len() == 1 => {
+            let ident = &named.named[0].ident;
+            quote!(#ident)
+        }
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => quote!(0),
+        _ => {
+            return Err(Error::new(
+                fields.span(),
+                "Nullable can only be derived for structs with a single field",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::stevia::pod::Nullable for #name #type_generics #where_clause {
+            #[inline]
+            fn is_some(&self) -> bool {
+                ::stevia::pod::Nullable::is_some(&self.#field)
+            }
+
+            #[inline]
+            fn is_none(&self) -> bool {
+                ::stevia::pod::Nullable::is_none(&self.#field)
+            }
+        }
+    })
+}
+
+/// Derives the `ZeroCopy` trait, checking the layout of the struct at compile time.
+///
+/// The struct must be `repr(C)` or `repr(transparent)`, its fields must be `Pod` and
+/// it must not have implicit padding, i.e., its size must be the sum of the sizes of
+/// its fields. These are the requirements of a sound `Pod` implementation, so they
+/// are also checked when `Pod` is implemented manually. Generic structs are not
+/// supported.
+///
+/// ```
+/// use bytemuck::{Pod, Zeroable};
+/// use stevia::ZeroCopy;
+///
+/// #[repr(C, packed)]
+/// #[derive(Clone, Copy, Pod, Zeroable, ZeroCopy)]
+/// struct Packed {
+///     flag: u8,
+///     amount: u64,
+/// }
+///
+/// let bytes = [1, 2, 0, 0, 0, 0, 0, 0, 0];
+/// assert_eq!({ Packed::load(&bytes).amount }, 2);
+/// ```
+///
+/// Structs with padding or without a `repr(C)` or `repr(transparent)` representation
+/// are rejected with a compile error.
+#[proc_macro_derive(ZeroCopy)]
+pub fn derive_zero_copy(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match zero_copy_impl(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn zero_copy_impl(input: &DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "ZeroCopy can only be derived for structs",
+            ))
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "ZeroCopy cannot be derived for generic structs",
+        ));
+    }
+
+    let mut stable_layout = false;
+
+    for attribute in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") || meta.path.is_ident("transparent") {
+                stable_layout = true;
+            }
+            // skips the arguments of representations such as `align(8)`
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+
+    if !stable_layout {
+        return Err(Error::new(
+            input.ident.span(),
+            "ZeroCopy requires the struct to be `repr(C)` or `repr(transparent)`",
+        ));
+    }
+
+    let name = &input.ident;
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+
+    Ok(quote! {
+        const _: () = {
+            fn assert_pod<T: ::stevia::__private::bytemuck::Pod>() {}
+            #( let _ = assert_pod::<#types>; )*
+
+            assert!(
+                ::core::mem::size_of::<#name>() == 0 #( + ::core::mem::size_of::<#types>() )*,
+                "ZeroCopy type has padding bytes"
+            );
+        };
+
+        impl ::stevia::ZeroCopy for #name {}
+    })
+}
@@ -0,0 +1,17 @@
+/// Checks the code accepted and the errors reported by the derive macros.
+///
+/// The expected errors include diagnostics of the compiler, which change between
+/// versions, so the test only runs on the toolchain used by CI:
+///
+/// ```text
+/// cargo +1.95.0 test -p stevia-derive --test ui -- --ignored
+/// ```
+///
+/// Set `TRYBUILD=overwrite` to update the expected errors.
+#[test]
+#[ignore = "diagnostics depend on the compiler version"]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
@@ -0,0 +1,9 @@
+use stevia::pod::Nullable;
+
+#[derive(Clone, Copy, Nullable)]
+enum Key {
+    None,
+    Some,
+}
+
+fn main() {}
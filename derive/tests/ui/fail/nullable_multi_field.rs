@@ -0,0 +1,8 @@
+use bytemuck::{Pod, Zeroable};
+use stevia::pod::{Nullable, Sentinel};
+
+#[repr(C)]
+#[derive(Clone, Copy, Nullable, Pod, Zeroable)]
+struct Pair(Sentinel<[u8; 32], 0>, Sentinel<[u8; 32], 0>);
+
+fn main() {}
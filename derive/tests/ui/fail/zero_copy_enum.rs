@@ -0,0 +1,9 @@
+use stevia::ZeroCopy;
+
+#[derive(Clone, Copy, ZeroCopy)]
+enum Kind {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use bytemuck::{Pod, Zeroable};
+use stevia::ZeroCopy;
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, ZeroCopy)]
+struct Padded {
+    flag: u8,
+    amount: u64,
+}
+
+unsafe impl Pod for Padded {}
+
+fn main() {}
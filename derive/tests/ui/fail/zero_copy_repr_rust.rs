@@ -0,0 +1,12 @@
+use bytemuck::{Pod, Zeroable};
+use stevia::ZeroCopy;
+
+#[derive(Clone, Copy, Zeroable, ZeroCopy)]
+struct Record {
+    id: u64,
+    amount: u64,
+}
+
+unsafe impl Pod for Record {}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use bytemuck::{Pod, Zeroable};
+use stevia::pod::{Nullable, PodOption, Sentinel};
+
+#[repr(transparent)]
+#[derive(Clone, Copy, Nullable, Pod, Zeroable)]
+struct Key(Sentinel<[u8; 32], 0>);
+
+#[repr(transparent)]
+#[derive(Clone, Copy, Nullable, Pod, Zeroable)]
+struct Named {
+    key: Key,
+}
+
+fn main() {
+    assert!(PodOption::new(Key(Sentinel::none())).value().is_none());
+    assert!(Named {
+        key: Key(Sentinel::new([1; 32])),
+    }
+    .is_some());
+}
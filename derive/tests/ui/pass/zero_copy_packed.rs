@@ -0,0 +1,19 @@
+use bytemuck::{Pod, Zeroable};
+use stevia::ZeroCopy;
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable, ZeroCopy)]
+struct Packed {
+    flag: u8,
+    amount: u64,
+}
+
+#[repr(transparent)]
+#[derive(Clone, Copy, Pod, Zeroable, ZeroCopy)]
+struct Amount(u64);
+
+fn main() {
+    let bytes = [1, 2, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!({ Packed::load(&bytes).amount }, 2);
+    assert_eq!(Amount::load(&bytes[1..]).0, 2);
+}
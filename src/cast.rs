@@ -0,0 +1,131 @@
+use bytemuck::Pod;
+use core::mem::{align_of, size_of};
+
+use crate::TranslationError;
+
+/// Returns an error if the length of the bytes is not the expected one.
+#[inline]
+pub(crate) fn check_length(expected: usize, actual: usize) -> Result<(), TranslationError> {
+    if expected != actual {
+        return Err(TranslationError::InvalidLength { expected, actual });
+    }
+
+    Ok(())
+}
+
+/// Returns an error if the bytes are not aligned for `T`.
+///
+/// The actual alignment reported is the largest power of two that divides the
+/// address of the bytes.
+#[inline]
+pub(crate) fn check_alignment<T>(bytes: &[u8]) -> Result<(), TranslationError> {
+    let address = bytes.as_ptr() as usize;
+    let required = align_of::<T>();
+
+    // alignments are always a power of two
+    if address & (required - 1) != 0 {
+        return Err(TranslationError::Misaligned {
+            required,
+            actual: address & address.wrapping_neg(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns an error if the length of the bytes is not a multiple of the size of `T`.
+#[inline]
+fn check_slice_length<T>(bytes: &[u8]) -> Result<(), TranslationError> {
+    let actual = bytes.len();
+    // a zero-sized type can only be cast from empty bytes
+    let expected = actual
+        .checked_rem(size_of::<T>())
+        .map_or(0, |slop| actual - slop);
+
+    check_length(expected, actual)
+}
+
+/// Casts the bytes to a reference of `T`.
+#[inline]
+pub(crate) fn try_from_bytes<T: Pod>(bytes: &[u8]) -> Result<&T, TranslationError> {
+    check_length(size_of::<T>(), bytes.len())?;
+    check_alignment::<T>(bytes)?;
+
+    Ok(bytemuck::from_bytes(bytes))
+}
+
+/// Casts the bytes to a mutable reference of `T`.
+#[inline]
+pub(crate) fn try_from_bytes_mut<T: Pod>(bytes: &mut [u8]) -> Result<&mut T, TranslationError> {
+    check_length(size_of::<T>(), bytes.len())?;
+    check_alignment::<T>(bytes)?;
+
+    Ok(bytemuck::from_bytes_mut(bytes))
+}
+
+/// Casts a slice of `A` to a slice of `B`.
+///
+/// The length of the result is derived from the byte length of the values, so the
+/// sizes of `A` and `B` can differ.
+#[inline]
+pub(crate) fn try_cast_slice<A: Pod, B: Pod>(values: &[A]) -> Result<&[B], TranslationError> {
+    let bytes: &[u8] = bytemuck::cast_slice(values);
+    check_alignment::<B>(bytes)?;
+    check_slice_length::<B>(bytes)?;
+
+    Ok(bytemuck::cast_slice(values))
+}
+
+/// Casts a mutable slice of `A` to a mutable slice of `B`.
+#[inline]
+pub(crate) fn try_cast_slice_mut<A: Pod, B: Pod>(
+    values: &mut [A],
+) -> Result<&mut [B], TranslationError> {
+    let bytes: &[u8] = bytemuck::cast_slice(values);
+    check_alignment::<B>(bytes)?;
+    check_slice_length::<B>(bytes)?;
+
+    Ok(bytemuck::cast_slice_mut(values))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cast::{try_cast_slice, try_cast_slice_mut},
+        TranslationError,
+    };
+
+    #[test]
+    fn test_cast_slice_wider() {
+        // u32 storage guarantees the alignment of the u16 values
+        let mut data = [0x0002_0001u32, 0x0004_0003];
+        let values: &mut [u16] = bytemuck::cast_slice_mut(&mut data);
+        assert_eq!(values.len(), 4);
+
+        let wide: &[u32] = try_cast_slice(values).unwrap();
+        assert_eq!(wide.len(), 2);
+        assert_eq!(wide, [0x0002_0001, 0x0004_0003]);
+
+        let wide: &mut [u32] = try_cast_slice_mut(values).unwrap();
+        wide[1] = 5;
+        assert_eq!(data, [0x0002_0001, 5]);
+
+        let values: &[u16] = bytemuck::cast_slice(&data);
+        // odd number of u16 values
+        assert_eq!(
+            try_cast_slice::<u16, u32>(&values[..3]),
+            Err(TranslationError::InvalidLength {
+                expected: 4,
+                actual: 6
+            })
+        );
+        // starts in the middle of a u32
+        assert_eq!(
+            try_cast_slice::<u16, u32>(&values[1..3]),
+            Err(TranslationError::Misaligned {
+                required: 4,
+                actual: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use bytemuck::{Pod, Zeroable};
+use core::{mem::size_of, ops::Deref};
+
+use super::FromBytes;
+use crate::types::Prefix;
+
+/// Macro to implement the readonly interface for an array map type.
+macro_rules! readonly_impl {
+    ( $name:tt ) => {
+        impl<'a, K, V> $name<'a, K, V>
+        where
+            K: Copy + Clone + Default + Ord + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+        {
+            /// Returns true if the map contains a value for the specified key.
+            pub fn contains_key(&self, key: &K) -> bool {
+                self.index(key).is_ok()
+            }
+
+            /// Returns a reference to the value corresponding to the key, if any.
+            pub fn get(&self, key: &K) -> Option<&V> {
+                if let Ok(index) = self.index(key) {
+                    Some(&self.entries[index].value)
+                } else {
+                    None
+                }
+            }
+
+            #[inline(always)]
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            #[inline(always)]
+            pub fn is_full(&self) -> bool {
+                self.len() == self.entries.len()
+            }
+
+            #[inline(always)]
+            pub fn len(&self) -> usize {
+                self.length.as_usize()
+            }
+
+            /// Returns the index of the entry for the key in the array.
+            ///
+            /// If the key is present, `Ok` is returned with the index of its entry;
+            /// otherwise `Err` is returned with the index where an entry for the key
+            /// should be inserted.
+            fn index(&self, key: &K) -> Result<usize, usize> {
+                self.entries[..self.len()].binary_search_by(|entry| entry.key.cmp(key))
+            }
+        }
+
+        impl<'a, K, V> Deref for $name<'a, K, V>
+        where
+            K: Copy + Clone + Default + Ord + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+        {
+            type Target = [Entry<K, V>];
+
+            fn deref(&self) -> &Self::Target {
+                &self.entries[..self.len()]
+            }
+        }
+    };
+}
+
+/// Macro to implement an array map type.
+macro_rules! prefix_array_map {
+    ( $name:tt, $prefix_type:tt ) => {
+        /// A map-like type that stores entries in an array sorted by key.
+        ///
+        /// It requires that the keys implement the `Ord` trait. It is a logic error
+        /// for a key to be modified in such a way that the key's order, as determined by
+        /// the [`Ord`] trait, or its equality, as determined by the [`Eq`] trait, changes while
+        /// it is in the map.
+        #[derive(Clone, Copy)]
+        pub struct $name<'a, K, V>
+        where
+            K: Copy + Clone + Default + Ord + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+        {
+            /// Number of entries in the array
+            ///
+            /// This number reflects the used positions.
+            length: &'a $prefix_type,
+
+            /// Array of entries.
+            entries: &'a [Entry<K, V>],
+        }
+
+        impl<'a, K, V> $name<'a, K, V>
+        where
+            K: Copy + Clone + Default + Ord + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+        {
+            /// Returns the maximum number of elements the map can hold, which is
+            /// limited by the range of the length prefix.
+            pub const fn max_capacity() -> usize {
+                <$prefix_type as Prefix>::MAX_CAPACITY
+            }
+
+            /// Loads a sorted map from its byte representation.
+            pub fn from_bytes(bytes: &'a [u8]) -> Self {
+                // fails to compile for entry types with padding bytes
+                let () = Entry::<K, V>::NO_PADDING;
+
+                let (length, entries) = bytes.split_at(size_of::<$prefix_type>());
+                Self {
+                    length: bytemuck::from_bytes(length),
+                    entries: bytemuck::cast_slice(entries),
+                }
+            }
+        }
+
+        impl<'a, K, V> FromBytes<'a> for $name<'a, K, V>
+        where
+            K: Copy + Clone + Default + Ord + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+        {
+            fn from_bytes(bytes: &'a [u8]) -> Self {
+                $name::from_bytes(bytes)
+            }
+        }
+    };
+}
+
+prefix_array_map!(U8ArrayMap, u8);
+prefix_array_map!(U16ArrayMap, u16);
+prefix_array_map!(U32ArrayMap, u32);
+prefix_array_map!(U64ArrayMap, u64);
+
+readonly_impl!(U8ArrayMap);
+readonly_impl!(U16ArrayMap);
+readonly_impl!(U32ArrayMap);
+readonly_impl!(U64ArrayMap);
+
+/// Macro to implement a mutable array map type.
+macro_rules! prefix_array_map {
+    ( $name:tt, $prefix_type:tt ) => {
+        /// A mutable map-like type that stores entries in an array sorted by key.
+        pub struct $name<'a, K, V>
+        where
+            K: Copy + Clone + Default + Ord + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+        {
+            /// Number of entries in the array
+            ///
+            /// This number reflects the used positions.
+            length: &'a mut $prefix_type,
+
+            /// Array of entries.
+            entries: &'a mut [Entry<K, V>],
+        }
+
+        impl<'a, K, V> $name<'a, K, V>
+        where
+            K: Copy + Clone + Default + Ord + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+        {
+            /// Returns the maximum number of elements the map can hold, which is
+            /// limited by the range of the length prefix.
+            pub const fn max_capacity() -> usize {
+                <$prefix_type as Prefix>::MAX_CAPACITY
+            }
+
+            pub fn from_bytes_mut(bytes: &'a mut [u8]) -> Self {
+                // fails to compile for entry types with padding bytes
+                let () = Entry::<K, V>::NO_PADDING;
+
+                let (length, entries) = bytes.split_at_mut(size_of::<$prefix_type>());
+                Self {
+                    length: bytemuck::from_bytes_mut(length),
+                    entries: bytemuck::cast_slice_mut(entries),
+                }
+            }
+
+            /// Returns a mutable reference to the value corresponding to the key, if any.
+            pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+                if let Ok(index) = self.index(key) {
+                    Some(&mut self.entries[index].value)
+                } else {
+                    None
+                }
+            }
+
+            /// Inserts a key-value pair into the map.
+            ///
+            /// Returns whether the entry was newly inserted. That is:
+            ///
+            /// - If the map did not previously contain the key, `true` is returned.
+            /// - If the map already contained the key, `false` is returned,
+            ///   and the map is not modified.
+            /// - If the map is full, `false` is returned.
+            pub fn insert(&mut self, key: K, value: V) -> bool {
+                // does not attempt to insert if the array is full
+                if self.is_full() {
+                    return false;
+                }
+
+                if let Err(index) = self.index(&key) {
+                    let length = self.len();
+                    // does not attempt to insert if the length would overflow
+                    // the prefix type
+                    if !self.length.checked_increment() {
+                        return false;
+                    }
+                    // move the entries to create space for the new entry
+                    self.entries.copy_within(index..length, index + 1);
+                    // insert the new entry
+                    self.entries[index] = Entry { key, value };
+                    return true;
+                }
+
+                false
+            }
+
+            /// Removes a key from the map, returning its value if the key was
+            /// present in the map.
+            pub fn remove(&mut self, key: &K) -> Option<V> {
+                if let Ok(index) = self.index(key) {
+                    let value = self.entries[index].value;
+                    let length = self.len();
+                    debug_assert!(length > 0, "length prefix underflow");
+
+                    if !self.length.checked_decrement() {
+                        return None;
+                    }
+                    // move the entries after the one being removed
+                    self.entries.copy_within(index + 1..length, index);
+                    return Some(value);
+                }
+
+                None
+            }
+        }
+    };
+}
+
+prefix_array_map!(U8ArrayMapMut, u8);
+prefix_array_map!(U16ArrayMapMut, u16);
+prefix_array_map!(U32ArrayMapMut, u32);
+prefix_array_map!(U64ArrayMapMut, u64);
+
+readonly_impl!(U8ArrayMapMut);
+readonly_impl!(U16ArrayMapMut);
+readonly_impl!(U32ArrayMapMut);
+readonly_impl!(U64ArrayMapMut);
+
+/// An entry of an array map.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct Entry<
+    K: Copy + Clone + Default + Ord + Pod + Zeroable,
+    V: Copy + Clone + Default + Pod + Zeroable,
+> {
+    /// The key of the entry.
+    key: K,
+    /// The value associated with the key.
+    value: V,
+}
+
+impl<
+        K: Copy + Clone + Default + Ord + Pod + Zeroable,
+        V: Copy + Clone + Default + Pod + Zeroable,
+    > Entry<K, V>
+{
+    /// Asserts that the entry has no padding between or after its fields, which
+    /// is required for the `Pod` implementation to be sound.
+    ///
+    /// This is evaluated when an array map is loaded for a specific key and value
+    /// type.
+    const NO_PADDING: () = assert!(
+        size_of::<Entry<K, V>>() == size_of::<K>() + size_of::<V>(),
+        "entry type must not have padding bytes"
+    );
+
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the value of the entry.
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+}
+
+unsafe impl<
+        K: Copy + Clone + Default + Ord + Pod + Zeroable,
+        V: Copy + Clone + Default + Pod + Zeroable,
+    > Zeroable for Entry<K, V>
+{
+}
+
+unsafe impl<
+        K: Copy + Clone + Default + Ord + Pod + Zeroable,
+        V: Copy + Clone + Default + Pod + Zeroable,
+    > Pod for Entry<K, V>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{vec, vec::Vec};
+
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let mut bytes = vec![0; size_of::<u64>() + 10 * size_of::<Entry<u64, u64>>()];
+        let mut map = U64ArrayMapMut::<u64, u64>::from_bytes_mut(&mut bytes);
+
+        assert!(map.insert(10, 100));
+        assert!(map.insert(1, 10));
+        assert!(map.insert(7, 70));
+        assert!(map.insert(4, 40));
+        // the key is already present
+        assert!(!map.insert(4, 0));
+
+        let map = U64ArrayMap::<u64, u64>::from_bytes(&bytes);
+        assert_eq!(map.len(), 4);
+
+        let keys: Vec<u64> = map.iter().map(|entry| *entry.key()).collect();
+        assert_eq!(keys, [1, 4, 7, 10]);
+
+        assert_eq!(map.get(&4), Some(&40));
+        assert_eq!(map.get(&10), Some(&100));
+        assert_eq!(map.get(&2), None);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut bytes = vec![0; size_of::<u32>() + 10 * size_of::<Entry<u32, u32>>()];
+        let mut map = U32ArrayMapMut::<u32, u32>::from_bytes_mut(&mut bytes);
+
+        map.insert(1, 10);
+        map.insert(10, 100);
+        map.insert(2, 20);
+        map.insert(7, 70);
+
+        assert_eq!(map.remove(&2), Some(20));
+        assert_eq!(map.remove(&2), None);
+        assert_eq!(map.len(), 3);
+
+        assert_eq!(map.remove(&10), Some(100));
+        assert_eq!(map.len(), 2);
+
+        let keys: Vec<u32> = map.iter().map(|entry| *entry.key()).collect();
+        assert_eq!(keys, [1, 7]);
+        assert_eq!(map.get(&7), Some(&70));
+    }
+
+    #[test]
+    fn test_insert_when_full() {
+        let mut bytes = vec![0; size_of::<u32>() + 3 * size_of::<Entry<u32, u32>>()];
+        let mut map = U32ArrayMapMut::<u32, u32>::from_bytes_mut(&mut bytes);
+
+        assert!(map.insert(3, 30));
+        assert!(map.insert(1, 10));
+        assert!(map.insert(2, 20));
+        assert!(map.is_full());
+
+        // we should not be able to insert when full
+        assert!(!map.insert(4, 40));
+
+        // when we remove an entry
+        assert_eq!(map.remove(&1), Some(10));
+        // we can insert again
+        assert!(map.insert(4, 40));
+
+        *map.get_mut(&4).unwrap() = 41;
+        assert_eq!(map.get(&4), Some(&41));
+    }
+}
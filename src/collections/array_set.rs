@@ -1,12 +1,223 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use bytemuck::{Pod, Zeroable};
-use std::{cmp::Ordering, mem::size_of, ops::Deref};
+use core::{cmp::Ordering, fmt::Debug, iter::Rev, marker::PhantomData, mem::size_of, ops::Deref};
+
+use super::FromBytes;
+use crate::{
+    cast,
+    types::{BePrefix, Prefix},
+    TranslationError,
+};
+
+/// Trait to define the order of the values of an array set.
+///
+/// The comparator must define a total order consistent with the equality of the
+/// values in the set, i.e., values are equal if and only if the comparator returns
+/// [`Ordering::Equal`] for them. The set uses the comparator to sort and find values,
+/// so an inconsistent comparator results in incorrect lookups.
+pub trait Comparator<V> {
+    /// Compares two values.
+    fn compare(a: &V, b: &V) -> Ordering;
+}
+
+/// Comparator that uses the natural order of values, as determined by the [`Ord`] trait.
+pub struct NaturalOrder;
+
+impl<V: Ord> Comparator<V> for NaturalOrder {
+    #[inline(always)]
+    fn compare(a: &V, b: &V) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Returns `true` if all values of `subset` are in `set`, where both slices are
+/// sorted by the comparator.
+///
+/// This walks both slices once, advancing on `set` until reaching each value of
+/// `subset`.
+fn is_sorted_subset<V, C: Comparator<V>>(subset: &[V], set: &[V]) -> bool {
+    let mut values = set.iter();
+
+    subset.iter().all(|target| loop {
+        match values.next().map(|value| C::compare(value, target)) {
+            Some(Ordering::Less) => continue,
+            Some(Ordering::Equal) => break true,
+            Some(Ordering::Greater) | None => break false,
+        }
+    })
+}
+
+/// Inserts a value at the specified index of the array, moving the values after it.
+///
+/// Returns `false` if the array is full or the length would overflow the prefix, in
+/// which case the array is not modified.
+fn insert_at<P: Prefix, V: Copy>(length: &mut P, values: &mut [V], index: usize, value: V) -> bool {
+    let current = length.as_usize();
+    // does not attempt to insert if the array is full
+    if current == values.len() {
+        return false;
+    }
+    // does not attempt to insert if the length would overflow
+    // the prefix type
+    if !length.checked_increment() {
+        return false;
+    }
+    // move the values to create space for the new value
+    values.copy_within(index..current, index + 1);
+    // insert the new value
+    values[index] = value;
+
+    true
+}
+
+/// A view into a single value of an array set, which is either present or vacant.
+///
+/// This is created by the `entry` method of mutable array sets.
+pub enum SetEntry<'a, V, P: Prefix> {
+    /// The value is present in the set.
+    Occupied(OccupiedSetEntry<'a, V>),
+
+    /// The value is not present in the set.
+    Vacant(VacantSetEntry<'a, V, P>),
+}
+
+impl<'a, V: Copy, P: Prefix> SetEntry<'a, V, P> {
+    /// Returns a mutable reference to the value in the set, inserting it if the entry
+    /// is vacant.
+    ///
+    /// Returns `None` if the entry is vacant and the set is full.
+    pub fn or_insert(self) -> Option<&'a mut V> {
+        match self {
+            SetEntry::Occupied(entry) => Some(entry.into_mut()),
+            SetEntry::Vacant(entry) => entry.insert(),
+        }
+    }
+}
+
+/// A view into a value present in an array set.
+///
+/// It is a logic error for the value to be modified in such a way that its order, as
+/// determined by the comparator of the set, changes; this would leave the set unsorted.
+pub struct OccupiedSetEntry<'a, V> {
+    /// The value in the set.
+    value: &'a mut V,
+}
+
+impl<'a, V> OccupiedSetEntry<'a, V> {
+    /// Returns a reference to the value in the set.
+    pub fn get(&self) -> &V {
+        self.value
+    }
+
+    /// Returns a mutable reference to the value in the set.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.value
+    }
+
+    /// Converts the entry into a mutable reference to the value in the set.
+    pub fn into_mut(self) -> &'a mut V {
+        self.value
+    }
+}
+
+/// A view into a value not present in an array set.
+pub struct VacantSetEntry<'a, V, P: Prefix> {
+    /// Length prefix of the set.
+    length: &'a mut P,
+
+    /// Values of the set.
+    values: &'a mut [V],
+
+    /// Position where the value should be inserted to keep the set sorted.
+    index: usize,
+
+    /// The value to insert.
+    value: V,
+}
+
+impl<'a, V: Copy, P: Prefix> VacantSetEntry<'a, V, P> {
+    /// Returns a reference to the value that would be inserted.
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Inserts the value in the set, returning a mutable reference to it.
+    ///
+    /// Returns `None` if the set is full, in which case the set is not modified.
+    pub fn insert(self) -> Option<&'a mut V> {
+        if insert_at(self.length, self.values, self.index, self.value) {
+            Some(&mut self.values[self.index])
+        } else {
+            None
+        }
+    }
+}
+
+/// An iterator that removes the values of an array set matching a predicate.
+///
+/// This is created by the `extract_if` method of mutable array sets.
+pub struct ExtractIf<'a, V: Copy, P: Prefix, F: FnMut(&V) -> bool> {
+    /// Length prefix of the set.
+    length: &'a mut P,
+
+    /// Values of the set.
+    values: &'a mut [V],
+
+    /// Predicate that determines the values to remove.
+    pred: F,
+
+    /// Position of the next value to check.
+    read: usize,
+
+    /// Position where the next retained value is moved to.
+    write: usize,
+
+    /// Number of values in the set before the extraction.
+    end: usize,
+}
+
+impl<'a, V: Copy, P: Prefix, F: FnMut(&V) -> bool> Iterator for ExtractIf<'a, V, P, F> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.read < self.end {
+            let value = self.values[self.read];
+            self.read += 1;
+
+            if (self.pred)(&value) {
+                return Some(value);
+            }
+            // retained values are moved back over the extracted ones, which keeps
+            // them in sorted order
+            self.values[self.write] = value;
+            self.write += 1;
+        }
+
+        None
+    }
+}
+
+impl<'a, V: Copy, P: Prefix, F: FnMut(&V) -> bool> Drop for ExtractIf<'a, V, P, F> {
+    fn drop(&mut self) {
+        // values not visited by the iterator are retained
+        let remaining = self.end - self.read;
+
+        if self.read > self.write {
+            self.values.copy_within(self.read..self.end, self.write);
+        }
+
+        self.length.set_usize(self.write + remaining);
+    }
+}
 
 /// Macro to implement the readonly interface for an array set type.
 macro_rules! readonly_impl {
     ( $name:tt ) => {
-        impl<'a, V> $name<'a, V>
+        impl<'a, V, C> $name<'a, V, C>
         where
-            V: Copy + Clone + Default + Ord + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
         {
             /// Returns true if the set contains a value.
             ///
@@ -29,6 +240,115 @@ macro_rules! readonly_impl {
                 }
             }
 
+            /// Returns the position of the value in the set, if present.
+            ///
+            /// Since values are kept sorted, the position is the rank of the value
+            /// (i.e., the number of values smaller than it).
+            pub fn index_of(&self, value: &V) -> Option<usize> {
+                self.index(value).0
+            }
+
+            /// Returns a reference to the value at the specified position, if any.
+            ///
+            /// Since values are kept sorted, this is the `index`-th smallest value of
+            /// the set.
+            pub fn get_at(&self, index: usize) -> Option<&V> {
+                if index < self.len() {
+                    Some(&self.values[index])
+                } else {
+                    None
+                }
+            }
+
+            /// Returns the greatest value in the set that is less than or equal to the
+            /// given value, if any.
+            pub fn floor(&self, value: &V) -> Option<&V> {
+                match self.index(value) {
+                    (Some(index), _) => Some(&self.values[index]),
+                    (None, insert) => insert
+                        .and_then(|index| index.checked_sub(1))
+                        .and_then(|index| self.get_at(index)),
+                }
+            }
+
+            /// Returns the least value in the set that is greater than or equal to the
+            /// given value, if any.
+            pub fn ceil(&self, value: &V) -> Option<&V> {
+                self.get_at(self.lower_bound(value))
+            }
+
+            /// Indicates whether the set is well-formed.
+            ///
+            /// A set is well-formed when its length fits in the array and its values are
+            /// in strictly ascending order, as determined by the comparator. Lookups on a
+            /// set that is not well-formed might return incorrect results.
+            pub fn is_valid(&self) -> bool {
+                self.len() <= self.capacity()
+                    && self
+                        .windows(2)
+                        .all(|pair| C::compare(&pair[0], &pair[1]) == Ordering::Less)
+            }
+
+            /// Returns `true` if all values of the set are in `other`.
+            ///
+            /// The values of `other` must be sorted by the same comparator, e.g., the
+            /// values of another array set, which can be passed directly since sets
+            /// dereference to a slice. Both sets are walked once, so this is O(n + m).
+            /// An empty set is a subset of any set.
+            pub fn is_subset(&self, other: &[V]) -> bool {
+                is_sorted_subset::<V, C>(self, other)
+            }
+
+            /// Returns `true` if all values of `other` are in the set.
+            ///
+            /// This is the mirror of [`is_subset`](Self::is_subset), with the same
+            /// requirements on the order of the values of `other`.
+            pub fn is_superset(&self, other: &[V]) -> bool {
+                is_sorted_subset::<V, C>(other, self)
+            }
+
+            /// Returns a copy of the bytes of the set, i.e., the length prefix and the
+            /// values array up to the capacity of the set.
+            ///
+            /// The bytes can be loaded as a set with the same values and capacity.
+            #[cfg(feature = "alloc")]
+            pub fn to_owned_bytes(&self) -> Vec<u8> {
+                let mut bytes = Vec::with_capacity(Self::data_len(self.capacity()));
+                bytes.extend_from_slice(bytemuck::bytes_of(&*self.length));
+                bytes.extend_from_slice(bytemuck::cast_slice(&*self.values));
+                bytes
+            }
+
+            /// Returns the values of the set as a slice.
+            ///
+            /// This is the same as dereferencing the set.
+            #[inline(always)]
+            pub fn as_slice(&self) -> &[V] {
+                &self.values[..self.len()]
+            }
+
+            /// Returns the number of bytes used by the set, i.e., the length prefix and the
+            /// values in the set.
+            ///
+            /// Since values are stored at the start of the array, the bytes after it are
+            /// unused and can be truncated when persisting the set.
+            pub fn used_len(&self) -> usize {
+                Self::data_len(self.len())
+            }
+
+            /// Returns the number of values the set can hold.
+            #[inline(always)]
+            pub fn capacity(&self) -> usize {
+                self.values.len()
+            }
+
+            /// Indicates whether the set has space for `additional` values.
+            pub fn can_insert(&self, additional: usize) -> bool {
+                self.len()
+                    .checked_add(additional)
+                    .is_some_and(|length| length <= self.capacity())
+            }
+
             #[inline(always)]
             pub fn is_empty(&self) -> bool {
                 self.len() == 0
@@ -41,7 +361,44 @@ macro_rules! readonly_impl {
 
             #[inline(always)]
             pub fn len(&self) -> usize {
-                *self.length as usize
+                self.length.as_usize()
+            }
+
+            /// Returns an iterator over the values in the range `[start, end)`, in ascending
+            /// order.
+            ///
+            /// The bounds of the range are located by binary search. The iterator is empty if
+            /// `start >= end`.
+            pub fn range(&self, start: &V, end: &V) -> core::slice::Iter<'_, V> {
+                let from = self.lower_bound(start);
+                let to = self.lower_bound(end).max(from);
+
+                self.values[from..to].iter()
+            }
+
+            /// Returns an iterator over the values in the range `[start, end)`, in descending
+            /// order.
+            ///
+            /// This is the same as reversing the iterator returned by [`Self::range`].
+            pub fn range_rev(&self, start: &V, end: &V) -> Rev<core::slice::Iter<'_, V>> {
+                self.range(start, end).rev()
+            }
+
+            /// Returns the index of the partition point according to the given predicate
+            /// (the index of the first value for which the predicate returns `false`).
+            ///
+            /// The set values are assumed to be partitioned according to the predicate,
+            /// as in [`slice::partition_point`]. Only the used positions of the array are
+            /// considered.
+            pub fn partition_point<F: Fn(&V) -> bool>(&self, pred: F) -> usize {
+                self.values[..self.len()].partition_point(pred)
+            }
+
+            /// Returns the position of the first value in the array that is not lower
+            /// than the specified value.
+            fn lower_bound(&self, value: &V) -> usize {
+                let (found, insert) = self.index(value);
+                found.or(insert).unwrap_or_default()
             }
 
             /// Returns the index of the value in the array.
@@ -61,12 +418,12 @@ macro_rules! readonly_impl {
                 }
 
                 let mut start = 0;
-                let mut end = (*self.length - 1) as usize;
+                let mut end = self.len() - 1;
 
                 while start <= end {
                     let middle = start + (end.saturating_sub(start) / 2);
 
-                    match value.cmp(&self.values[middle]) {
+                    match C::compare(value, &self.values[middle]) {
                         // if we are already at the start of the array, there are no
                         // more elements to check
                         Ordering::Less if end == start => break,
@@ -78,7 +435,7 @@ macro_rules! readonly_impl {
                         Ordering::Greater => start = middle.saturating_add(1),
 
                         // found the value in the array
-                        std::cmp::Ordering::Equal => {
+                        core::cmp::Ordering::Equal => {
                             return (Some(middle), None);
                         }
                     }
@@ -90,14 +447,49 @@ macro_rules! readonly_impl {
             }
         }
 
-        impl<'a, V> Deref for $name<'a, V>
+        impl<'a, V, C> Deref for $name<'a, V, C>
         where
-            V: Copy + Clone + Default + Ord + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
         {
             type Target = [V];
 
             fn deref(&self) -> &Self::Target {
-                &self.values[..self.len()]
+                self.as_slice()
+            }
+        }
+
+        impl<'a, V, C> Debug for $name<'a, V, C>
+        where
+            V: Copy + Clone + Debug + Default + Pod + Zeroable,
+            C: Comparator<V>,
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_set().entries(self.iter()).finish()
+            }
+        }
+
+        impl<'a, const N: usize> $name<'a, [u8; N], NaturalOrder>
+        where
+            [u8; N]: Default + Pod,
+        {
+            /// Returns the values that start with the specified byte prefix.
+            ///
+            /// Since values are sorted, values sharing a prefix are contiguous; the bounds
+            /// of the subslice are located by binary search. The subslice is empty if no
+            /// value starts with the prefix, including when the prefix is longer than the
+            /// values.
+            pub fn prefix_range(&self, prefix: &[u8]) -> &[[u8; N]] {
+                if prefix.len() > N {
+                    return &[];
+                }
+
+                let values = self.as_slice();
+                let start = values.partition_point(|value| &value[..prefix.len()] < prefix);
+                let end = start
+                    + values[start..].partition_point(|value| &value[..prefix.len()] == prefix);
+
+                &values[start..end]
             }
         }
     };
@@ -105,21 +497,22 @@ macro_rules! readonly_impl {
 
 /// Macro to implement an array set type.
 macro_rules! prefix_array_set {
-    ( $name:tt, $prefix_type:tt ) => {
+    ( $name:tt, $prefix_type:ty ) => {
         /// A set-like type that stores elements in a sorted array.
         ///
-        /// It requires that the elements implement the `Ord` trait. It is a logic error
-        /// for a value to be modified in such a way that the value's order, as determined by
-        /// the [`Ord`] trait, or its equality, as determined by the [`Eq`] trait, changes while
-        /// it is in the set.
+        /// By default, it requires that the elements implement the `Ord` trait; a custom
+        /// order can be specified with a [`Comparator`] type. It is a logic error for a
+        /// value to be modified in such a way that the value's order, as determined by
+        /// the comparator, changes while it is in the set.
         ///
         /// The behavior resulting from either logic error is not specified, but will
         /// be encapsulated to the `ArraySet` that observed the logic error and not
         /// result in undefined behavior. This could include panics, incorrect results,
         /// aborts, memory leaks, and non-termination.
-        pub struct $name<'a, V>
+        pub struct $name<'a, V, C = NaturalOrder>
         where
-            V: Copy + Clone + Default + PartialOrd + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
         {
             /// Number of elements in the array
             ///
@@ -128,19 +521,121 @@ macro_rules! prefix_array_set {
 
             /// Array of nodes to store the tree.
             values: &'a [V],
+
+            /// Comparator that determines the order of the values.
+            comparator: PhantomData<fn() -> C>,
+        }
+
+        // manual implementations to avoid requiring the comparator to be `Clone` and `Copy`
+        impl<'a, V, C> Clone for $name<'a, V, C>
+        where
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
+        {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<'a, V, C> Copy for $name<'a, V, C>
+        where
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
+        {
         }
 
-        impl<'a, V> $name<'a, V>
+        impl<'a, V, C> $name<'a, V, C>
         where
-            V: Copy + Clone + Default + PartialOrd + Pod + Zeroable,
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
         {
+            /// Returns the maximum number of elements the set can hold, which is
+            /// limited by the range of the length prefix.
+            pub const fn max_capacity() -> usize {
+                <$prefix_type as Prefix>::MAX_CAPACITY
+            }
+
+            /// Returns the required data length (in bytes) to store a set with the specified capacity.
+            pub const fn data_len(capacity: usize) -> usize {
+                size_of::<$prefix_type>() + capacity * size_of::<V>()
+            }
+
             /// Loads a sorted array from its byte representation.
             pub fn from_bytes(bytes: &'a [u8]) -> Self {
                 let (length, values) = bytes.split_at(size_of::<$prefix_type>());
                 Self {
                     length: bytemuck::from_bytes(length),
                     values: bytemuck::cast_slice(values),
+                    comparator: PhantomData,
+                }
+            }
+
+            /// Loads a sorted array from its byte representation, validating that the
+            /// set is well-formed.
+            ///
+            /// This should be used when the bytes are not trusted, since lookups rely on
+            /// the values being sorted and unique. Returns [`TranslationError::InvalidValue`]
+            /// if the values are not in strictly ascending order.
+            pub fn from_bytes_validated(bytes: &'a [u8]) -> Result<Self, TranslationError> {
+                if bytes.len() < size_of::<$prefix_type>() {
+                    return Err(TranslationError::InvalidLength {
+                        expected: size_of::<$prefix_type>(),
+                        actual: bytes.len(),
+                    });
+                }
+
+                let (length, values) = bytes.split_at(size_of::<$prefix_type>());
+                let set = Self {
+                    length: cast::try_from_bytes(length)?,
+                    values: cast::try_cast_slice(values)?,
+                    comparator: PhantomData,
+                };
+
+                if set.len() > set.capacity() {
+                    return Err(TranslationError::InvalidLength {
+                        expected: Self::data_len(set.len()),
+                        actual: bytes.len(),
+                    });
+                }
+
+                if !set.is_valid() {
+                    return Err(TranslationError::InvalidValue);
                 }
+
+                Ok(set)
+            }
+
+            /// Returns the bytes of a set holding the given values, i.e., the length
+            /// prefix and the values sorted and deduplicated by the comparator.
+            ///
+            /// The capacity of the set is the number of distinct values, so the bytes
+            /// can be loaded with [`Self::from_bytes`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the number of distinct values exceeds [`Self::max_capacity`].
+            #[cfg(feature = "alloc")]
+            pub fn from_vec(mut values: Vec<V>) -> Vec<u8> {
+                values.sort_unstable_by(C::compare);
+                values.dedup_by(|a, b| C::compare(a, b) == Ordering::Equal);
+
+                let mut length = <$prefix_type>::zeroed();
+                length.set_usize(values.len());
+
+                let mut bytes = Vec::with_capacity(Self::data_len(values.len()));
+                bytes.extend_from_slice(bytemuck::bytes_of(&length));
+                bytes.extend_from_slice(bytemuck::cast_slice(&values));
+                bytes
+            }
+        }
+
+        impl<'a, V, C> FromBytes<'a> for $name<'a, V, C>
+        where
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
+        {
+            fn from_bytes(bytes: &'a [u8]) -> Self {
+                $name::from_bytes(bytes)
             }
         }
     };
@@ -150,19 +645,29 @@ prefix_array_set!(U8ArraySet, u8);
 prefix_array_set!(U16ArraySet, u16);
 prefix_array_set!(U32ArraySet, u32);
 prefix_array_set!(U64ArraySet, u64);
+prefix_array_set!(U16ArraySetBe, BePrefix<u16>);
+prefix_array_set!(U32ArraySetBe, BePrefix<u32>);
+prefix_array_set!(U64ArraySetBe, BePrefix<u64>);
 
 readonly_impl!(U8ArraySet);
 readonly_impl!(U16ArraySet);
 readonly_impl!(U32ArraySet);
 readonly_impl!(U64ArraySet);
+readonly_impl!(U16ArraySetBe);
+readonly_impl!(U32ArraySetBe);
+readonly_impl!(U64ArraySetBe);
 
 /// Macro to implement a mutable array set type.
 macro_rules! prefix_array_set {
-    ( $name:tt, $prefix_type:tt ) => {
+    ( $name:tt, $prefix_type:ty ) => {
         /// A mutable set-like type that stores elements in a sorted array.
-        pub struct $name<'a, V>
+        ///
+        /// The order of the elements is determined by the [`Comparator`] type, which
+        /// defaults to their natural order.
+        pub struct $name<'a, V, C = NaturalOrder>
         where
-            V: Default + Copy + Clone + Ord + Pod + Zeroable,
+            V: Default + Copy + Clone + Pod + Zeroable,
+            C: Comparator<V>,
         {
             /// Number of elements in the array
             ///
@@ -171,17 +676,33 @@ macro_rules! prefix_array_set {
 
             /// Array of nodes to store the tree.
             values: &'a mut [V],
+
+            /// Comparator that determines the order of the values.
+            comparator: PhantomData<fn() -> C>,
         }
 
-        impl<'a, V> $name<'a, V>
+        impl<'a, V, C> $name<'a, V, C>
         where
-            V: Default + Copy + Clone + Ord + Pod + Zeroable,
+            V: Default + Copy + Clone + Pod + Zeroable,
+            C: Comparator<V>,
         {
+            /// Returns the maximum number of elements the set can hold, which is
+            /// limited by the range of the length prefix.
+            pub const fn max_capacity() -> usize {
+                <$prefix_type as Prefix>::MAX_CAPACITY
+            }
+
+            /// Returns the required data length (in bytes) to store a set with the specified capacity.
+            pub const fn data_len(capacity: usize) -> usize {
+                size_of::<$prefix_type>() + capacity * size_of::<V>()
+            }
+
             pub fn from_bytes_mut(bytes: &'a mut [u8]) -> Self {
                 let (length, values) = bytes.split_at_mut(size_of::<$prefix_type>());
                 Self {
                     length: bytemuck::from_bytes_mut(length),
                     values: bytemuck::cast_slice_mut(values),
+                    comparator: PhantomData,
                 }
             }
 
@@ -212,26 +733,136 @@ macro_rules! prefix_array_set {
             ///   and the value passed as argument is dropped.
             /// - If the set is full, `false` is returned.
             pub fn insert(&mut self, value: V) -> bool {
-                // does not attempt to insert if the array is full
-                if self.is_full() {
-                    return false;
+                if let (_, Some(index)) = self.index(&value) {
+                    return self.insert_at_index(index, value);
                 }
 
-                if let (_, Some(index)) = self.index(&value) {
-                    unsafe {
-                        let ptr = self.values.as_mut_ptr();
-                        let src_ptr = ptr.add(index);
-                        let dest_ptr = ptr.add(index + 1);
-                        // move the bytes to create space for the new value
-                        std::ptr::copy(src_ptr, dest_ptr, self.values.len() - index);
+                false
+            }
+
+            /// Adds a value to the set, returning the position where it was inserted.
+            ///
+            /// Since values are kept sorted, the position is the rank of the value in the
+            /// set, which can be used to update data stored in parallel to the set. Returns
+            /// `None` if the set already contained the value or the set is full; in both
+            /// cases, the set is not modified.
+            pub fn checked_insert(&mut self, value: V) -> Option<usize> {
+                match self.index(&value) {
+                    (_, Some(index)) if self.insert_at_index(index, value) => Some(index),
+                    _ => None,
+                }
+            }
+
+            /// Adds a value to the set, replacing the existing value, if any, that is equal
+            /// to the given one.
+            ///
+            /// Returns `Ok` with the replaced value. If the set did not contain an equal
+            /// value, the value is inserted and `Ok(None)` is returned. If the value needs
+            /// to be inserted but the set is full, the set is not modified and `Err` is
+            /// returned with the value.
+            pub fn replace(&mut self, value: V) -> Result<Option<V>, V> {
+                match self.index(&value) {
+                    (Some(index), _) => {
+                        Ok(Some(core::mem::replace(&mut self.values[index], value)))
                     }
-                    // insert the new value
-                    self.values[index] = value;
-                    *self.length += 1;
-                    return true;
+                    (_, Some(index)) if self.insert_at_index(index, value) => Ok(None),
+                    _ => Err(value),
                 }
+            }
 
-                false
+            /// Returns a mutable reference to the value in the set that is equal to the
+            /// given value, inserting it if it is not present.
+            ///
+            /// Returns `None` if the value is not present and the set is full.
+            ///
+            /// It is a logic error for a value to be modified in such a way that the value's order,
+            /// as determined by the [`Ord`] trait, or its equality, as determined by the [`Eq`] trait,
+            /// changes while it is in the set.
+            pub fn get_or_insert(&mut self, value: V) -> Option<&mut V> {
+                let index = match self.index(&value) {
+                    (Some(index), _) => index,
+                    (_, Some(index)) if self.insert_at_index(index, value) => index,
+                    _ => return None,
+                };
+
+                Some(&mut self.values[index])
+            }
+
+            /// Moves all values greater than or equal to `pivot` to the end of `dest`.
+            ///
+            /// The values are moved with a single copy, since they are stored contiguously at
+            /// the end of the set. Returns [`TranslationError::CapacityExceeded`] if `dest` does
+            /// not have space for the values, or [`TranslationError::InvalidValue`] if `dest`
+            /// has a value that is not lower than `pivot`, since `dest` would no longer be
+            /// sorted; in both cases, neither set is modified. When no value is greater
+            /// than or equal to `pivot`, this does nothing.
+            pub fn split_off(
+                &mut self,
+                pivot: &V,
+                dest: &mut $name<'_, V, C>,
+            ) -> Result<(), TranslationError> {
+                let index = self.lower_bound(pivot);
+                let length = self.len();
+
+                if index == length {
+                    return Ok(());
+                }
+
+                if let Some(last) = dest.last() {
+                    if C::compare(last, pivot) != Ordering::Less {
+                        return Err(TranslationError::InvalidValue);
+                    }
+                }
+
+                let start = dest.len();
+                let end = start + (length - index);
+
+                if end > dest.capacity() || end > Self::max_capacity() {
+                    return Err(TranslationError::CapacityExceeded);
+                }
+
+                dest.values[start..end].copy_from_slice(&self.values[index..length]);
+                dest.length.set_usize(end);
+                self.length.set_usize(index);
+
+                Ok(())
+            }
+
+            /// Returns the values of the set as a mutable slice.
+            ///
+            /// It is a logic error to modify the values in such a way that they are no
+            /// longer sorted or unique, as determined by the comparator, unless the set
+            /// is restored with [`Self::sort_and_dedup`] before it is used again.
+            #[inline(always)]
+            pub fn as_mut_slice(&mut self) -> &mut [V] {
+                let length = self.len();
+                &mut self.values[..length]
+            }
+
+            /// Returns the entry of the value in the set for in-place manipulation.
+            ///
+            /// The position of the value is found with a single binary search, which is
+            /// reused to insert the value when the entry is vacant.
+            pub fn entry(&mut self, value: V) -> SetEntry<'_, V, $prefix_type> {
+                match self.index(&value) {
+                    (Some(index), _) => SetEntry::Occupied(OccupiedSetEntry {
+                        value: &mut self.values[index],
+                    }),
+                    (_, index) => SetEntry::Vacant(VacantSetEntry {
+                        length: &mut *self.length,
+                        values: &mut *self.values,
+                        index: index.unwrap_or_default(),
+                        value,
+                    }),
+                }
+            }
+
+            /// Inserts a value at the specified index, moving the values after it.
+            ///
+            /// The index must be the position where the value should be inserted to
+            /// keep the set sorted.
+            fn insert_at_index(&mut self, index: usize, value: V) -> bool {
+                insert_at(self.length, self.values, index, value)
             }
 
             /// Removes a value from the set and returns whether the value was present in the set.
@@ -254,63 +885,297 @@ macro_rules! prefix_array_set {
 
                 if let (Some(index), _) = self.index(value) {
                     let value = self.values[index];
+                    let length = self.len();
+                    debug_assert!(length > 0, "length prefix underflow");
+
+                    if !self.length.checked_decrement() {
+                        return None;
+                    }
 
                     // only need to copy bytes around if the element being removed
                     // is not the last element in the array
-                    if index < self.len() - 1 {
+                    if index < length - 1 {
                         unsafe {
                             let ptr = self.values.as_mut_ptr();
                             let src_ptr = ptr.add(index + 1);
                             let dest_ptr = ptr.add(index);
                             // move the bytes after the value being removed
-                            std::ptr::copy(src_ptr, dest_ptr, self.values.len() - index);
+                            core::ptr::copy(src_ptr, dest_ptr, length - index - 1);
                         }
                     }
-                    *self.length -= 1;
                     return Some(value);
                 }
 
                 None
             }
-        }
-    };
-}
 
-prefix_array_set!(U8ArraySetMut, u8);
-prefix_array_set!(U16ArraySetMut, u16);
-prefix_array_set!(U32ArraySetMut, u32);
-prefix_array_set!(U64ArraySetMut, u64);
+            /// Removes the values in the range `[start, end)`, returning the number of
+            /// values removed.
+            ///
+            /// The bounds of the range are located by binary search and the values after
+            /// the range are moved once, so this is O(n) regardless of the number of values
+            /// removed. An empty range (`start >= end`) does not remove any value.
+            pub fn remove_range(&mut self, start: &V, end: &V) -> usize {
+                let from = self.lower_bound(start);
+                let to = self.lower_bound(end);
 
-readonly_impl!(U8ArraySetMut);
-readonly_impl!(U16ArraySetMut);
-readonly_impl!(U32ArraySetMut);
-readonly_impl!(U64ArraySetMut);
+                if from >= to {
+                    return 0;
+                }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+                let length = self.len();
+                self.values.copy_within(to..length, from);
 
-    #[test]
-    fn test_insert() {
-        let mut bytes = vec![0; size_of::<u64>() + 10 * size_of::<u8>()];
-        let mut set = U64ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+                let removed = to - from;
+                self.length.set_usize(length - removed);
 
-        set.insert(10);
-        set.insert(1);
-        set.insert(2);
-        set.insert(7);
-        set.insert(4);
+                removed
+            }
 
-        let set = U64ArraySet::<u8>::from_bytes(&bytes);
-        assert_eq!(set.len(), 5);
-        assert_eq!(&*set, &[1, 2, 4, 7, 10]);
+            /// Returns an iterator that removes and yields the values matching the
+            /// predicate, in ascending order.
+            ///
+            /// The remaining values are moved to the start of the array as the iterator
+            /// advances, so the set stays sorted. If the iterator is dropped before being
+            /// fully consumed, the values not yet visited are retained.
+            ///
+            /// The set is empty while the iterator is alive and its length is only
+            /// restored when the iterator is dropped. If the iterator is leaked (e.g.,
+            /// with [`core::mem::forget`]), the set is left empty instead of holding
+            /// duplicated values.
+            pub fn extract_if<F: FnMut(&V) -> bool>(
+                &mut self,
+                pred: F,
+            ) -> ExtractIf<'_, V, $prefix_type, F> {
+                let end = self.len();
+                // the length is restored when the iterator is dropped
+                self.length.set_usize(0);
 
-        assert!(set.get(&1).is_some());
-    }
+                ExtractIf {
+                    length: self.length,
+                    values: self.values,
+                    pred,
+                    read: 0,
+                    write: 0,
+                    end,
+                }
+            }
 
-    #[test]
-    fn test_remove() {
-        let mut bytes = vec![0; size_of::<u64>() + 10 * size_of::<u8>()];
+            /// Removes and returns the first (smallest) value of the set, if any.
+            ///
+            /// This requires moving all the remaining values, so it is O(n).
+            pub fn pop_first(&mut self) -> Option<V> {
+                let first = *self.first()?;
+                self.take(&first)
+            }
+
+            /// Removes and returns the last (largest) value of the set, if any.
+            ///
+            /// No values need to be moved, so it is O(1).
+            pub fn pop_last(&mut self) -> Option<V> {
+                let value = *self.last()?;
+                debug_assert!(!self.is_empty(), "length prefix underflow");
+
+                if !self.length.checked_decrement() {
+                    return None;
+                }
+
+                Some(value)
+            }
+
+            /// Removes and returns the value in the set, if any, that is equal to the given one,
+            /// without preserving the order of the remaining values.
+            ///
+            /// The removed value is swapped with the last value of the set, which avoids moving
+            /// the values after it. This leaves the set unsorted: until [`Self::sort`] is called,
+            /// lookups and insertions are a logic error, since they rely on the values being sorted.
+            ///
+            /// The value is found by a linear scan, so this can be called repeatedly before
+            /// the set is sorted again.
+            pub fn take_unsorted(&mut self, value: &V) -> Option<V> {
+                let length = self.len();
+                let position = self.values[..length]
+                    .iter()
+                    .position(|v| C::compare(v, value) == Ordering::Equal);
+
+                if let Some(index) = position {
+                    let value = self.values[index];
+                    debug_assert!(length > 0, "length prefix underflow");
+                    let last = length - 1;
+
+                    if !self.length.checked_decrement() {
+                        return None;
+                    }
+                    self.values.swap(index, last);
+
+                    return Some(value);
+                }
+
+                None
+            }
+
+            /// Sorts the values of the set.
+            ///
+            /// This restores the order of the set after values were removed using
+            /// [`Self::take_unsorted`].
+            pub fn sort(&mut self) {
+                let length = self.len();
+                self.values[..length].sort_unstable_by(C::compare);
+            }
+
+            /// Sorts the values of the set and removes duplicated values, updating the
+            /// length of the set.
+            ///
+            /// This restores the invariants of the set after its backing bytes are modified
+            /// directly, which can leave values out of order or duplicated. This is O(n log n).
+            pub fn sort_and_dedup(&mut self) {
+                self.sort();
+
+                let length = self.len();
+                let mut unique = usize::from(length > 0);
+
+                for index in 1..length {
+                    if C::compare(&self.values[index], &self.values[unique - 1]) != Ordering::Equal
+                    {
+                        self.values[unique] = self.values[index];
+                        unique += 1;
+                    }
+                }
+                // clears the positions of the removed values
+                self.values[unique..length].fill(V::default());
+                // the number of unique values is at most the current length, so it
+                // fits the prefix type
+                self.length.set_usize(unique);
+            }
+        }
+    };
+}
+
+prefix_array_set!(U8ArraySetMut, u8);
+prefix_array_set!(U16ArraySetMut, u16);
+prefix_array_set!(U32ArraySetMut, u32);
+prefix_array_set!(U64ArraySetMut, u64);
+prefix_array_set!(U16ArraySetBeMut, BePrefix<u16>);
+prefix_array_set!(U32ArraySetBeMut, BePrefix<u32>);
+prefix_array_set!(U64ArraySetBeMut, BePrefix<u64>);
+
+readonly_impl!(U8ArraySetMut);
+readonly_impl!(U16ArraySetMut);
+readonly_impl!(U32ArraySetMut);
+readonly_impl!(U64ArraySetMut);
+readonly_impl!(U16ArraySetBeMut);
+readonly_impl!(U32ArraySetBeMut);
+readonly_impl!(U64ArraySetBeMut);
+
+/// Macro to implement equality between array set types.
+///
+/// Values are compared using the comparator, so two sets are equal if they contain
+/// the same values; since values are sorted, this is an element-wise comparison of
+/// the values in the sets.
+macro_rules! partial_eq_impl {
+    ( $name:tt, $other:tt ) => {
+        partial_eq_impl!(@impl $name, $name);
+        partial_eq_impl!(@impl $name, $other);
+        partial_eq_impl!(@impl $other, $name);
+        partial_eq_impl!(@impl $other, $other);
+
+        impl<'a, V, C> Eq for $name<'a, V, C>
+        where
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
+        {
+        }
+
+        impl<'a, V, C> Eq for $other<'a, V, C>
+        where
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
+        {
+        }
+    };
+    ( @impl $name:tt, $other:tt ) => {
+        impl<'a, 'b, V, C> PartialEq<$other<'b, V, C>> for $name<'a, V, C>
+        where
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
+        {
+            fn eq(&self, other: &$other<'b, V, C>) -> bool {
+                self.len() == other.len()
+                    && self
+                        .iter()
+                        .zip(other.iter())
+                        .all(|(a, b)| C::compare(a, b) == Ordering::Equal)
+            }
+        }
+    };
+}
+
+partial_eq_impl!(U8ArraySet, U8ArraySetMut);
+partial_eq_impl!(U16ArraySet, U16ArraySetMut);
+partial_eq_impl!(U32ArraySet, U32ArraySetMut);
+partial_eq_impl!(U64ArraySet, U64ArraySetMut);
+partial_eq_impl!(U16ArraySetBe, U16ArraySetBeMut);
+partial_eq_impl!(U32ArraySetBe, U32ArraySetBeMut);
+partial_eq_impl!(U64ArraySetBe, U64ArraySetBeMut);
+
+/// Macro to implement the read-only view of a mutable array set type.
+macro_rules! as_readonly_impl {
+    ( $name:tt, $readonly:tt ) => {
+        impl<'a, V, C> $name<'a, V, C>
+        where
+            V: Copy + Clone + Default + Pod + Zeroable,
+            C: Comparator<V>,
+        {
+            /// Returns a read-only view of the set.
+            ///
+            /// The view borrows the same data, so there is no need to load the bytes
+            /// again; the set cannot be modified while the view is in use.
+            pub fn as_readonly(&self) -> $readonly<'_, V, C> {
+                $readonly {
+                    length: self.length,
+                    values: self.values,
+                    comparator: PhantomData,
+                }
+            }
+        }
+    };
+}
+
+as_readonly_impl!(U8ArraySetMut, U8ArraySet);
+as_readonly_impl!(U16ArraySetMut, U16ArraySet);
+as_readonly_impl!(U32ArraySetMut, U32ArraySet);
+as_readonly_impl!(U64ArraySetMut, U64ArraySet);
+as_readonly_impl!(U16ArraySetBeMut, U16ArraySetBe);
+as_readonly_impl!(U32ArraySetBeMut, U32ArraySetBe);
+as_readonly_impl!(U64ArraySetBeMut, U64ArraySetBe);
+
+#[cfg(test)]
+mod tests {
+    use std::{format, vec, vec::Vec};
+
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let mut bytes = vec![0; size_of::<u64>() + 10 * size_of::<u8>()];
+        let mut set = U64ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+
+        set.insert(10);
+        set.insert(1);
+        set.insert(2);
+        set.insert(7);
+        set.insert(4);
+
+        let set = U64ArraySet::<u8>::from_bytes(&bytes);
+        assert_eq!(set.len(), 5);
+        assert_eq!(&*set, &[1, 2, 4, 7, 10]);
+
+        assert!(set.get(&1).is_some());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut bytes = vec![0; size_of::<u64>() + 10 * size_of::<u8>()];
         let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
 
         set.insert(1);
@@ -354,4 +1219,992 @@ mod tests {
         assert!(set.get(&10).is_some());
         assert!(set.get_mut(&10).is_some());
     }
+
+    #[test]
+    fn test_take_unsorted() {
+        let mut bytes = vec![0; size_of::<u8>() + 10 * size_of::<u8>()];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+
+        set.insert(1);
+        set.insert(10);
+        set.insert(2);
+        set.insert(7);
+        set.insert(4);
+
+        assert_eq!(set.take_unsorted(&2), Some(2));
+        assert_eq!(set.take_unsorted(&3), None);
+        assert_eq!(set.len(), 4);
+        // the last value takes the place of the removed one
+        assert_eq!(&*set, &[1, 10, 4, 7]);
+
+        set.sort();
+        assert_eq!(&*set, &[1, 4, 7, 10]);
+        assert!(set.contains(&10));
+    }
+
+    #[test]
+    fn test_take_unsorted_twice() {
+        let mut bytes = vec![0; size_of::<u8>() + 10 * size_of::<u8>()];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+
+        for value in [7, 1, 10, 4, 2] {
+            set.insert(value);
+        }
+
+        assert_eq!(set.take_unsorted(&1), Some(1));
+        assert_eq!(&*set, &[10, 2, 4, 7]);
+        // the set is no longer sorted, so a binary search would miss this value
+        assert_eq!(set.take_unsorted(&10), Some(10));
+        assert_eq!(set.take_unsorted(&3), None);
+
+        set.sort();
+        assert_eq!(&*set, &[2, 4, 7]);
+    }
+
+    #[test]
+    fn test_index_of() {
+        let mut bytes = vec![0; size_of::<u32>() + 10 * size_of::<u32>()];
+        let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut bytes);
+
+        set.insert(30);
+        set.insert(10);
+        set.insert(50);
+        set.insert(20);
+        set.insert(40);
+
+        let set = U32ArraySet::<u32>::from_bytes(&bytes);
+
+        for value in [10, 20, 30, 40, 50] {
+            let index = set.index_of(&value).unwrap();
+            assert_eq!(set.get_at(index), Some(&value));
+        }
+
+        assert_eq!(set.index_of(&10), Some(0));
+        assert_eq!(set.index_of(&50), Some(4));
+        assert_eq!(set.index_of(&35), None);
+        assert_eq!(set.get_at(5), None);
+    }
+
+    #[test]
+    fn test_insert_prefix_overflow() {
+        let mut bytes = vec![0; size_of::<u8>() + 300 * size_of::<u8>()];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+
+        for value in 0..u8::MAX {
+            assert!(set.insert(value));
+        }
+
+        assert_eq!(set.len(), u8::MAX as usize);
+        assert!(!set.is_full());
+
+        // the length prefix cannot represent more values
+        assert!(!set.insert(u8::MAX));
+        assert_eq!(set.len(), u8::MAX as usize);
+        assert!(!set.contains(&u8::MAX));
+    }
+
+    #[test]
+    fn test_pop() {
+        let mut bytes = vec![0; size_of::<u8>() + 10 * size_of::<u8>()];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+
+        set.insert(4);
+        set.insert(1);
+        set.insert(10);
+        set.insert(7);
+        set.insert(2);
+
+        assert_eq!(set.pop_first(), Some(1));
+        assert_eq!(set.pop_last(), Some(10));
+        assert_eq!(&*set, &[2, 4, 7]);
+
+        assert_eq!(set.pop_first(), Some(2));
+        assert_eq!(set.pop_first(), Some(4));
+        assert_eq!(set.pop_last(), Some(7));
+
+        assert!(set.is_empty());
+        assert_eq!(set.pop_first(), None);
+        assert_eq!(set.pop_last(), None);
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut bytes = vec![0; size_of::<u8>() + 10 * size_of::<u8>()];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+
+        set.insert(2);
+        set.insert(1);
+        set.insert(3);
+
+        let set = U8ArraySet::<u8>::from_bytes(&bytes);
+        let copy = set;
+
+        assert_eq!(&*set, &[1, 2, 3]);
+        assert_eq!(&*copy, &[1, 2, 3]);
+        assert!(copy.contains(&2));
+        assert_eq!(copy.len(), set.len());
+    }
+
+    #[test]
+    fn test_can_insert() {
+        let mut bytes = vec![0; size_of::<u8>() + 5 * size_of::<u8>()];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+
+        assert_eq!(set.capacity(), 5);
+        assert!(set.can_insert(5));
+        assert!(!set.can_insert(6));
+
+        set.insert(1);
+        set.insert(2);
+        set.insert(3);
+        set.insert(4);
+
+        // one short of full
+        assert!(set.can_insert(1));
+        assert!(!set.can_insert(2));
+
+        set.insert(5);
+
+        // exactly full
+        assert!(set.can_insert(0));
+        assert!(!set.can_insert(1));
+
+        // does not overflow the length
+        assert!(!set.can_insert(usize::MAX));
+    }
+
+    #[test]
+    fn test_capacity_from_byte_length() {
+        // the capacity is derived from the byte length of the values, not the
+        // number of bytes
+        let bytes = vec![0; size_of::<u64>() + 10 * size_of::<u16>()];
+        let set = U64ArraySet::<u16>::from_bytes(&bytes);
+        assert_eq!(set.capacity(), 10);
+
+        let bytes = vec![0; size_of::<u64>() + 10 * size_of::<u64>()];
+        let set = U64ArraySet::<u64>::from_bytes(&bytes);
+        assert_eq!(set.capacity(), 10);
+    }
+
+    #[test]
+    fn test_get_or_insert() {
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, Default, Pod, Zeroable)]
+        struct Counter {
+            key: u32,
+            count: u32,
+        }
+
+        impl PartialEq for Counter {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+
+        impl Eq for Counter {}
+
+        impl PartialOrd for Counter {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Counter {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.key.cmp(&other.key)
+            }
+        }
+
+        let mut bytes = vec![0; size_of::<u64>() + 3 * size_of::<Counter>()];
+        let mut set = U64ArraySetMut::<Counter>::from_bytes_mut(&mut bytes);
+
+        for key in [3, 1, 3, 2, 3] {
+            let counter = set.get_or_insert(Counter { key, count: 0 }).unwrap();
+            // only the count is mutated, so the order is preserved
+            counter.count += 1;
+        }
+
+        let counters: Vec<(u32, u32)> = set.iter().map(|c| (c.key, c.count)).collect();
+        assert_eq!(counters, [(1, 1), (2, 1), (3, 3)]);
+
+        // the set is full, so new values cannot be inserted
+        assert!(set.get_or_insert(Counter { key: 4, count: 0 }).is_none());
+        assert!(set.get_or_insert(Counter { key: 2, count: 0 }).is_some());
+    }
+
+    #[test]
+    fn test_max_capacity() {
+        const _: () = assert!(U8ArraySet::<u8>::max_capacity() == u8::MAX as usize);
+
+        assert_eq!(U8ArraySetMut::<u8>::max_capacity(), 255);
+        assert_eq!(U16ArraySet::<u8>::max_capacity(), 65_535);
+        assert_eq!(U32ArraySetMut::<u8>::max_capacity(), u32::MAX as usize);
+        assert_eq!(U64ArraySet::<u8>::max_capacity(), u64::MAX as usize);
+    }
+
+    #[test]
+    fn test_sort_and_dedup() {
+        let mut bytes = vec![0; size_of::<u32>() + 10 * size_of::<u32>()];
+        let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut bytes);
+
+        for value in [1, 3, 5, 7] {
+            set.insert(value);
+        }
+
+        // writes values directly to the backing bytes, breaking the order
+        // and adding duplicates
+        bytes[..4].copy_from_slice(&7u32.to_le_bytes());
+        for (index, value) in [7u32, 3, 1, 5, 3, 7, 2].iter().enumerate() {
+            let offset = size_of::<u32>() * (index + 1);
+            bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+
+        let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut bytes);
+        set.sort_and_dedup();
+
+        assert_eq!(set.len(), 5);
+        assert_eq!(*set, [1, 2, 3, 5, 7]);
+
+        for value in [1, 2, 3, 5, 7] {
+            assert!(set.contains(&value));
+        }
+        assert!(set.insert(4));
+        assert_eq!(*set, [1, 2, 3, 4, 5, 7]);
+
+        // an empty set is not modified
+        let mut bytes = vec![0; size_of::<u32>() + 2 * size_of::<u32>()];
+        let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut bytes);
+        set.sort_and_dedup();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut bytes = vec![0; size_of::<u32>() + 10 * size_of::<u32>()];
+        let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut bytes);
+
+        for value in [3, 1, 2] {
+            set.insert(value);
+        }
+        assert_eq!(format!("{:?}", set), "{1, 2, 3}");
+
+        let set = U32ArraySet::<u32>::from_bytes(&bytes);
+        assert_eq!(format!("{:?}", set), "{1, 2, 3}");
+    }
+
+    #[test]
+    fn test_data_len() {
+        const DATA_LEN: usize = U8ArraySetMut::<u8>::data_len(10);
+        let mut bytes = [0u8; DATA_LEN];
+        assert_eq!(bytes.len(), 11);
+
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+        assert_eq!(set.capacity(), 10);
+
+        for value in 0..10 {
+            assert!(set.insert(value));
+        }
+        assert!(set.is_full());
+
+        assert_eq!(U32ArraySet::<u64>::data_len(3), 28);
+        assert_eq!(U64ArraySet::<u32>::data_len(0), 8);
+    }
+
+    #[test]
+    fn test_replace() {
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+        struct Entry {
+            key: u32,
+            value: u32,
+        }
+
+        impl PartialOrd for Entry {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Entry {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.key.cmp(&other.key)
+            }
+        }
+
+        let mut bytes = vec![0; size_of::<u32>() + 2 * size_of::<Entry>()];
+        let mut set = U32ArraySetMut::<Entry>::from_bytes_mut(&mut bytes);
+
+        // inserts a new value
+        assert_eq!(set.replace(Entry { key: 2, value: 20 }), Ok(None));
+        assert_eq!(set.replace(Entry { key: 1, value: 10 }), Ok(None));
+        assert_eq!(set.len(), 2);
+
+        // replaces an existing value
+        assert_eq!(
+            set.replace(Entry { key: 2, value: 21 }),
+            Ok(Some(Entry { key: 2, value: 20 }))
+        );
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            *set,
+            [Entry { key: 1, value: 10 }, Entry { key: 2, value: 21 }]
+        );
+
+        // the set is full, so new values are given back
+        assert_eq!(
+            set.replace(Entry { key: 3, value: 30 }),
+            Err(Entry { key: 3, value: 30 })
+        );
+        assert!(!set.contains(&Entry { key: 3, value: 0 }));
+    }
+
+    #[test]
+    fn test_comparator() {
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+        struct Record {
+            key: u32,
+            weight: f32,
+        }
+
+        // records are ordered and compared by key only
+        struct ByKey;
+
+        impl Comparator<Record> for ByKey {
+            fn compare(a: &Record, b: &Record) -> Ordering {
+                a.key.cmp(&b.key)
+            }
+        }
+
+        let mut bytes = vec![0; U32ArraySetMut::<Record, ByKey>::data_len(5)];
+        let mut set = U32ArraySetMut::<Record, ByKey>::from_bytes_mut(&mut bytes);
+
+        for (key, weight) in [(3, 0.3), (1, 0.1), (2, 0.2)] {
+            assert!(set.insert(Record { key, weight }));
+        }
+        // equality is determined by the key
+        assert!(!set.insert(Record {
+            key: 2,
+            weight: 0.5
+        }));
+
+        let set = U32ArraySet::<Record, ByKey>::from_bytes(&bytes);
+        let keys: Vec<u32> = set.iter().map(|record| record.key).collect();
+        assert_eq!(keys, [1, 2, 3]);
+
+        let record = set.get(&Record {
+            key: 2,
+            weight: 0.0,
+        });
+        assert_eq!(record.map(|record| record.weight), Some(0.2));
+        assert!(!set.contains(&Record {
+            key: 4,
+            weight: 0.0
+        }));
+    }
+
+    #[test]
+    fn test_big_endian_prefix() {
+        let mut bytes = vec![0; U32ArraySetBe::<u32>::data_len(10)];
+        // big-endian encoded length
+        bytes[..4].copy_from_slice(&3u32.to_be_bytes());
+        for (index, value) in [1u32, 2, 3].iter().enumerate() {
+            let offset = size_of::<u32>() * (index + 1);
+            bytes[offset..offset + 4].copy_from_slice(&value.to_ne_bytes());
+        }
+
+        let set = U32ArraySetBe::<u32>::from_bytes(&bytes);
+        assert_eq!(set.len(), 3);
+        assert_eq!(*set, [1, 2, 3]);
+
+        let mut set = U32ArraySetBeMut::<u32>::from_bytes_mut(&mut bytes);
+        assert!(set.insert(4));
+        assert!(set.remove(&1));
+        assert!(set.insert(0));
+        assert!(set.insert(5));
+        assert_eq!(set.len(), 5);
+
+        set.sort_and_dedup();
+        assert_eq!(*set, [0, 2, 3, 4, 5]);
+        assert_eq!(bytes[..4], 5u32.to_be_bytes());
+
+        assert_eq!(U16ArraySetBe::<u8>::max_capacity(), u16::MAX as usize);
+    }
+
+    #[test]
+    fn test_used_len() {
+        let mut bytes = vec![0; U32ArraySet::<u32>::data_len(10)];
+        let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut bytes);
+        assert_eq!(set.used_len(), size_of::<u32>());
+
+        set.insert(2);
+        set.insert(1);
+        set.insert(3);
+        assert_eq!(set.used_len(), size_of::<u32>() + 3 * size_of::<u32>());
+
+        // the used bytes can be loaded on their own
+        let used_len = set.used_len();
+        let set = U32ArraySet::<u32>::from_bytes(&bytes[..used_len]);
+        assert_eq!(*set, [1, 2, 3]);
+        assert!(set.is_full());
+    }
+
+    #[test]
+    fn test_partial_eq() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+        for value in [3, 1, 4, 5, 9] {
+            set.insert(value);
+        }
+
+        let mut other_bytes = vec![0; U8ArraySet::<u8>::data_len(5)];
+        let mut other = U8ArraySetMut::<u8>::from_bytes_mut(&mut other_bytes);
+        for value in [9, 5, 4, 1, 3] {
+            other.insert(value);
+        }
+
+        assert!(set == other);
+        other.remove(&4);
+        assert!(set != other);
+        other.insert(2);
+        assert!(set != other);
+        other.remove(&2);
+        other.insert(4);
+
+        // between read-only and mutable sets
+        assert!(set == U8ArraySet::<u8>::from_bytes(&other_bytes));
+        assert_eq!(
+            U8ArraySet::<u8>::from_bytes(&bytes),
+            U8ArraySet::<u8>::from_bytes(&other_bytes)
+        );
+    }
+
+    #[test]
+    fn test_pod_str_values() {
+        use crate::pod::PodStr;
+
+        let mut bytes = vec![0; U8ArraySet::<PodStr<16>>::data_len(5)];
+        let mut set = U8ArraySetMut::<PodStr<16>>::from_bytes_mut(&mut bytes);
+
+        for value in ["pear", "apple", "fig", "apple", "banana"] {
+            set.insert(PodStr::from(value));
+        }
+
+        let set = U8ArraySet::<PodStr<16>>::from_bytes(&bytes);
+        let values: Vec<&str> = set.iter().map(|value| value.as_str().unwrap()).collect();
+        assert_eq!(values, ["apple", "banana", "fig", "pear"]);
+        assert!(set.contains(&PodStr::from("fig")));
+        assert!(!set.contains(&PodStr::from("grape")));
+    }
+
+    #[test]
+    fn test_remove_range() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+        for value in [1, 3, 5, 7, 9, 11] {
+            set.insert(value);
+        }
+
+        // interior range, with bounds not in the set
+        assert_eq!(set.remove_range(&4, &8), 2);
+        assert_eq!(*set, [1, 3, 9, 11]);
+
+        // empty ranges
+        assert_eq!(set.remove_range(&4, &8), 0);
+        assert_eq!(set.remove_range(&9, &3), 0);
+
+        // leading range, with the end bound excluded
+        assert_eq!(set.remove_range(&0, &9), 2);
+        assert_eq!(*set, [9, 11]);
+
+        // trailing range
+        set.insert(5);
+        assert_eq!(set.remove_range(&9, &u8::MAX), 2);
+        assert_eq!(*set, [5]);
+
+        assert_eq!(set.remove_range(&0, &u8::MAX), 1);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_prefix_range() {
+        let mut bytes = vec![0; U8ArraySet::<[u8; 3]>::data_len(10)];
+        let mut set = U8ArraySetMut::<[u8; 3]>::from_bytes_mut(&mut bytes);
+        for value in [
+            [2, 0, 0],
+            [1, 2, 3],
+            [1, 2, 0],
+            [1, 3, 0],
+            [0, 9, 9],
+            [1, 0, 5],
+            [3, 1, 1],
+        ] {
+            set.insert(value);
+        }
+
+        let set = U8ArraySet::<[u8; 3]>::from_bytes(&bytes);
+
+        assert_eq!(
+            set.prefix_range(&[1]),
+            [[1, 0, 5], [1, 2, 0], [1, 2, 3], [1, 3, 0]]
+        );
+        assert_eq!(set.prefix_range(&[1, 2]), [[1, 2, 0], [1, 2, 3]]);
+        assert_eq!(set.prefix_range(&[1, 2, 3]), [[1, 2, 3]]);
+        assert_eq!(set.prefix_range(&[3]), [[3, 1, 1]]);
+        // no values sharing the prefix
+        assert!(set.prefix_range(&[1, 1]).is_empty());
+        assert!(set.prefix_range(&[4]).is_empty());
+        assert!(set.prefix_range(&[0, 0]).is_empty());
+        // prefix longer than the values
+        assert!(set.prefix_range(&[1, 2, 3, 4]).is_empty());
+        // an empty prefix matches all values
+        assert_eq!(set.prefix_range(&[]).len(), 7);
+    }
+
+    #[test]
+    fn test_extract_if() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+        for value in [6, 1, 2, 9, 4, 3, 8, 7] {
+            set.insert(value);
+        }
+
+        let even: Vec<u8> = set.extract_if(|value| value % 2 == 0).collect();
+        assert_eq!(even, [2, 4, 6, 8]);
+        assert_eq!(set.len(), 4);
+        assert_eq!(*set, [1, 3, 7, 9]);
+        assert!(set.is_valid());
+
+        // no matches
+        assert_eq!(set.extract_if(|value| *value > 10).count(), 0);
+        assert_eq!(*set, [1, 3, 7, 9]);
+
+        // partially consumed, keeping the values not visited
+        let mut iter = set.extract_if(|value| *value > 1);
+        assert_eq!(iter.next(), Some(3));
+        drop(iter);
+        assert_eq!(*set, [1, 7, 9]);
+        assert!(set.is_valid());
+
+        // all values
+        assert_eq!(set.extract_if(|_| true).count(), 3);
+        assert!(set.is_empty());
+
+        for value in [1, 7, 9] {
+            set.insert(value);
+        }
+
+        // leaking the iterator leaves the set empty
+        let mut iter = set.extract_if(|value| *value > 1);
+        assert_eq!(iter.next(), Some(7));
+        core::mem::forget(iter);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_subset_superset() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+        for value in [1, 3, 5, 7] {
+            set.insert(value);
+        }
+        let set = U8ArraySet::<u8>::from_bytes(&bytes);
+
+        let mut other_bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut other = U8ArraySetMut::<u8>::from_bytes_mut(&mut other_bytes);
+
+        // empty set
+        assert!(other.is_subset(&set));
+        assert!(set.is_superset(&other));
+        assert!(!set.is_subset(&other));
+
+        // proper subset
+        other.insert(3);
+        other.insert(7);
+        assert!(other.is_subset(&set));
+        assert!(!other.is_superset(&set));
+        assert!(set.is_superset(&other));
+        assert!(!set.is_subset(&other));
+
+        // equal sets
+        other.insert(1);
+        other.insert(5);
+        assert!(other.is_subset(&set));
+        assert!(other.is_superset(&set));
+
+        // value not in the set
+        other.insert(6);
+        assert!(!other.is_subset(&set));
+        assert!(other.is_superset(&set));
+
+        // disjoint sets
+        assert!(!set.is_subset(&[0, 2, 4]));
+        assert!(!set.is_superset(&[0, 2, 4]));
+        assert!(!set.is_superset(&[8]));
+    }
+
+    #[test]
+    fn test_insert_take_cycles() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(3)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+
+        for round in 0..100u8 {
+            // removing from an empty set leaves the length untouched
+            assert_eq!(set.take(&round), None);
+            assert_eq!(set.take_unsorted(&round), None);
+            assert_eq!(set.pop_first(), None);
+            assert_eq!(set.pop_last(), None);
+            assert_eq!(set.len(), 0);
+
+            set.insert(round);
+            set.insert(round.wrapping_add(1));
+            assert_eq!(set.take(&round), Some(round));
+            assert_eq!(set.pop_last(), Some(round.wrapping_add(1)));
+            assert_eq!(set.take(&round), None);
+        }
+
+        assert!(set.is_empty());
+        assert_eq!(bytes[0], 0);
+    }
+
+    #[test]
+    fn test_entry() {
+        use core::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+        static COMPARISONS: AtomicUsize = AtomicUsize::new(0);
+
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+        struct Counter {
+            key: u32,
+            count: u32,
+        }
+
+        // counts the number of comparisons performed
+        struct ByKey;
+
+        impl Comparator<Counter> for ByKey {
+            fn compare(a: &Counter, b: &Counter) -> Ordering {
+                COMPARISONS.fetch_add(1, AtomicOrdering::Relaxed);
+                a.key.cmp(&b.key)
+            }
+        }
+
+        let mut bytes = vec![0; U32ArraySet::<Counter, ByKey>::data_len(3)];
+        let mut set = U32ArraySetMut::<Counter, ByKey>::from_bytes_mut(&mut bytes);
+
+        for key in [5, 3, 5, 1, 5, 3] {
+            let counter = Counter { key, count: 0 };
+            let comparisons = COMPARISONS.load(AtomicOrdering::Relaxed);
+
+            match set.entry(counter) {
+                SetEntry::Occupied(mut entry) => entry.get_mut().count += 1,
+                SetEntry::Vacant(entry) => {
+                    assert_eq!(entry.value(), &counter);
+                    entry.insert().unwrap().count = 1;
+                }
+            }
+            // a single binary search over at most 3 values
+            assert!(COMPARISONS.load(AtomicOrdering::Relaxed) - comparisons <= 2);
+        }
+
+        let counts: Vec<(u32, u32)> = set.iter().map(|c| (c.key, c.count)).collect();
+        assert_eq!(counts, [(1, 1), (3, 2), (5, 3)]);
+
+        // the set is full
+        assert!(set
+            .entry(Counter { key: 4, count: 0 })
+            .or_insert()
+            .is_none());
+        assert_eq!(
+            set.entry(Counter { key: 3, count: 0 })
+                .or_insert()
+                .map(|c| c.count),
+            Some(2)
+        );
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_as_slice() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+        for value in [4, 1, 3] {
+            set.insert(value);
+        }
+        assert_eq!(set.as_slice(), [1, 3, 4]);
+
+        // in-place transform followed by restoring the order
+        set.as_mut_slice()
+            .iter_mut()
+            .for_each(|value| *value = 10 - *value % 3);
+        assert_eq!(set.as_mut_slice(), [9, 10, 9]);
+        set.sort_and_dedup();
+        assert_eq!(set.as_slice(), [9, 10]);
+
+        let set = U8ArraySet::<u8>::from_bytes(&bytes);
+        assert_eq!(set.as_slice(), [9, 10]);
+        assert_eq!(set.as_slice().len(), set.len());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_to_owned_bytes() {
+        let mut bytes = vec![0; U32ArraySet::<u32>::data_len(5)];
+        let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut bytes);
+        for value in [30, 10, 20] {
+            set.insert(value);
+        }
+
+        let mut owned = set.to_owned_bytes();
+        assert_eq!(owned, bytes);
+
+        // the owned bytes are independent of the original set
+        let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut owned);
+        set.insert(40);
+        assert_eq!(*set, [10, 20, 30, 40]);
+
+        let set = U32ArraySet::<u32>::from_bytes(&bytes);
+        assert_eq!(*set, [10, 20, 30]);
+        assert_eq!(set.to_owned_bytes(), bytes);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_from_vec() {
+        let bytes = U16ArraySetBe::<u16>::from_vec(vec![30, 10, 20, 10, 30]);
+        assert_eq!(bytes.len(), U16ArraySetBe::<u16>::data_len(3));
+
+        let set = U16ArraySetBe::<u16>::from_bytes(&bytes);
+        assert_eq!(*set, [10, 20, 30]);
+        assert_eq!(set.capacity(), 3);
+        assert_eq!(set.to_owned_bytes(), bytes);
+
+        let bytes = U8ArraySet::<u8>::from_vec(Vec::new());
+        let set = U8ArraySet::<u8>::from_bytes_validated(&bytes).unwrap();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+        for value in [1, 3, 5, 7, 9] {
+            set.insert(value);
+        }
+
+        let mut dest_bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut dest = U8ArraySetMut::<u8>::from_bytes_mut(&mut dest_bytes);
+
+        // pivot not present
+        set.split_off(&8, &mut dest).unwrap();
+        assert_eq!(*set, [1, 3, 5, 7]);
+        assert_eq!(*dest, [9]);
+
+        // dest would not be sorted
+        assert_eq!(
+            set.split_off(&5, &mut dest),
+            Err(TranslationError::InvalidValue)
+        );
+        assert_eq!(*set, [1, 3, 5, 7]);
+
+        // pivot present, moving it to a new set
+        let mut dest_bytes = vec![0; U8ArraySet::<u8>::data_len(2)];
+        let mut dest = U8ArraySetMut::<u8>::from_bytes_mut(&mut dest_bytes);
+        set.split_off(&5, &mut dest).unwrap();
+        assert_eq!(*set, [1, 3]);
+        assert_eq!(*dest, [5, 7]);
+
+        // nothing to move
+        set.split_off(&4, &mut dest).unwrap();
+        assert_eq!(*dest, [5, 7]);
+
+        let mut empty_bytes = vec![0; U8ArraySet::<u8>::data_len(2)];
+        let mut empty = U8ArraySetMut::<u8>::from_bytes_mut(&mut empty_bytes);
+        set.split_off(&4, &mut empty).unwrap();
+        assert!(empty.is_empty());
+
+        // dest does not have space
+        let mut small_bytes = vec![0; U8ArraySet::<u8>::data_len(1)];
+        let mut small = U8ArraySetMut::<u8>::from_bytes_mut(&mut small_bytes);
+        assert_eq!(
+            set.split_off(&0, &mut small),
+            Err(TranslationError::CapacityExceeded)
+        );
+
+        // moves all values
+        set.split_off(&0, &mut empty).unwrap();
+        assert!(set.is_empty());
+        assert_eq!(*empty, [1, 3]);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+        for value in [1, 3, 5, 7, 9] {
+            set.insert(value);
+        }
+
+        let set = U8ArraySet::<u8>::from_bytes(&bytes);
+
+        for (start, end) in [(3, 8), (0, 10), (4, 5), (9, 9), (7, 2), (2, 3)] {
+            let forward: Vec<u8> = set.range(&start, &end).copied().collect();
+            let mut reverse: Vec<u8> = set.range_rev(&start, &end).copied().collect();
+            reverse.reverse();
+
+            assert_eq!(forward, reverse);
+        }
+
+        assert!(set.range(&3, &8).eq(&[3, 5, 7]));
+        assert!(set.range_rev(&3, &8).eq(&[7, 5, 3]));
+        assert!(set.range_rev(&0, &u8::MAX).eq(&[9, 7, 5, 3, 1]));
+        assert_eq!(set.range(&7, &2).count(), 0);
+    }
+
+    #[test]
+    fn test_as_readonly() {
+        let mut bytes = vec![0; U32ArraySetBe::<u32>::data_len(5)];
+        let mut set = U32ArraySetBeMut::<u32>::from_bytes_mut(&mut bytes);
+        set.insert(3);
+        set.insert(1);
+
+        let view = set.as_readonly();
+        assert_eq!(view.len(), 2);
+        assert!(view.contains(&1));
+        assert_eq!(view.as_slice(), &[1, 3]);
+        assert!(view == set);
+
+        // the view reflects later changes to the set
+        set.insert(2);
+        set.remove(&3);
+
+        let view = set.as_readonly();
+        assert_eq!(view.as_slice(), &[1, 2]);
+        assert!(!view.contains(&3));
+        assert_eq!(view.floor(&5), Some(&2));
+    }
+
+    #[test]
+    fn test_floor_ceil() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+        assert_eq!(set.floor(&5), None);
+        assert_eq!(set.ceil(&5), None);
+
+        for value in [10, 20, 30] {
+            set.insert(value);
+        }
+
+        let set = U8ArraySet::<u8>::from_bytes(&bytes);
+
+        // between
+        assert_eq!(set.floor(&15), Some(&10));
+        assert_eq!(set.ceil(&15), Some(&20));
+        assert_eq!(set.floor(&29), Some(&20));
+        assert_eq!(set.ceil(&21), Some(&30));
+        // below
+        assert_eq!(set.floor(&5), None);
+        assert_eq!(set.ceil(&5), Some(&10));
+        // above
+        assert_eq!(set.floor(&35), Some(&30));
+        assert_eq!(set.ceil(&35), None);
+        // equal
+        assert_eq!(set.floor(&20), Some(&20));
+        assert_eq!(set.ceil(&20), Some(&20));
+        assert_eq!(set.floor(&10), Some(&10));
+        assert_eq!(set.ceil(&30), Some(&30));
+    }
+
+    #[test]
+    fn test_partition_point() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(10)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+        for value in [1, 3, 5, 7, 9] {
+            set.insert(value);
+        }
+
+        let set = U8ArraySet::<u8>::from_bytes(&bytes);
+
+        // matches a prefix
+        assert_eq!(set.partition_point(|v| *v < 5), 2);
+        assert_eq!(set.partition_point(|v| *v <= 5), 3);
+        // matches none
+        assert_eq!(set.partition_point(|v| *v < 1), 0);
+        // matches all, ignoring the unused positions
+        assert_eq!(set.partition_point(|v| *v > 0), 5);
+    }
+
+    #[test]
+    fn test_checked_insert() {
+        let mut bytes = vec![0; U8ArraySet::<u8>::data_len(5)];
+        let mut set = U8ArraySetMut::<u8>::from_bytes_mut(&mut bytes);
+
+        assert_eq!(set.checked_insert(5), Some(0));
+        // at the start
+        assert_eq!(set.checked_insert(1), Some(0));
+        // at the end
+        assert_eq!(set.checked_insert(9), Some(2));
+        // in the middle
+        assert_eq!(set.checked_insert(7), Some(2));
+        assert_eq!(*set, [1, 5, 7, 9]);
+
+        // already present
+        assert_eq!(set.checked_insert(7), None);
+
+        assert_eq!(set.checked_insert(3), Some(1));
+        // the set is full
+        assert_eq!(set.checked_insert(4), None);
+        assert_eq!(*set, [1, 3, 5, 7, 9]);
+    }
+
+    #[test]
+    fn test_from_bytes_validated() {
+        let mut bytes = vec![0; U32ArraySet::<u32>::data_len(5)];
+        let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut bytes);
+        set.insert(3);
+        set.insert(1);
+        set.insert(2);
+        assert!(set.is_valid());
+
+        let set = U32ArraySet::<u32>::from_bytes_validated(&bytes).unwrap();
+        assert_eq!(*set, [1, 2, 3]);
+
+        // unsorted values
+        let mut unsorted = bytes.clone();
+        unsorted[4..8].copy_from_slice(&5u32.to_ne_bytes());
+        assert!(!U32ArraySet::<u32>::from_bytes(&unsorted).is_valid());
+        assert_eq!(
+            U32ArraySet::<u32>::from_bytes_validated(&unsorted).err(),
+            Some(TranslationError::InvalidValue)
+        );
+
+        // duplicated values
+        let mut duplicated = bytes.clone();
+        duplicated[8..12].copy_from_slice(&1u32.to_ne_bytes());
+        assert_eq!(
+            U32ArraySet::<u32>::from_bytes_validated(&duplicated).err(),
+            Some(TranslationError::InvalidValue)
+        );
+
+        // length larger than the capacity
+        let mut overflow = bytes.clone();
+        overflow[..4].copy_from_slice(&6u32.to_ne_bytes());
+        assert!(!U32ArraySet::<u32>::from_bytes(&overflow).is_valid());
+        assert_eq!(
+            U32ArraySet::<u32>::from_bytes_validated(&overflow).err(),
+            Some(TranslationError::InvalidLength {
+                expected: U32ArraySet::<u32>::data_len(6),
+                actual: bytes.len()
+            })
+        );
+
+        assert_eq!(
+            U32ArraySet::<u32>::from_bytes_validated(&bytes[..2]).err(),
+            Some(TranslationError::InvalidLength {
+                expected: 4,
+                actual: 2
+            })
+        );
+    }
 }
@@ -1,5 +1,8 @@
+use alloc::vec::Vec;
 use bytemuck::{Pod, Zeroable};
-use std::{cmp::max, fmt::Debug};
+use core::{cmp::max, fmt::Debug};
+
+use super::FromBytes;
 
 /// Constant to represent an empty value.
 const SENTINEL: u32 = 0;
@@ -43,7 +46,7 @@ macro_rules! readonly_impl {
         {
             /// Returns the required data length (in bytes) to store a tree with the specified capacity.
             pub const fn data_len(capacity: usize) -> usize {
-                std::mem::size_of::<Allocator>() + (capacity * std::mem::size_of::<Node<K, V>>())
+                core::mem::size_of::<Allocator>() + (capacity * core::mem::size_of::<Node<K, V>>())
             }
 
             /// Returns the capacity of the tree.
@@ -128,6 +131,7 @@ macro_rules! readonly_impl {
 /// most.
 ///
 /// This type can be used to reference a read-only tree.
+#[derive(Clone, Copy)]
 pub struct AVLTree<
     'a,
     K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
@@ -150,7 +154,7 @@ impl<
 {
     /// Loads a tree from a byte array.
     pub fn from_bytes(bytes: &'a [u8]) -> Self {
-        let (allocator, nodes) = bytes.split_at(std::mem::size_of::<Allocator>());
+        let (allocator, nodes) = bytes.split_at(core::mem::size_of::<Allocator>());
 
         let allocator = bytemuck::from_bytes::<Allocator>(allocator);
         let nodes = bytemuck::cast_slice(nodes);
@@ -159,6 +163,17 @@ impl<
     }
 }
 
+impl<
+        'a,
+        K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+        V: Default + Copy + Clone + Pod + Zeroable,
+    > FromBytes<'a> for AVLTree<'a, K, V>
+{
+    fn from_bytes(bytes: &'a [u8]) -> Self {
+        AVLTree::from_bytes(bytes)
+    }
+}
+
 /// AVL tree struct, which is a self-balancing binary search tree. Values in the
 /// tree are stored as such the height of two sibling subtrees differ by one at
 /// most.
@@ -186,7 +201,7 @@ impl<
 {
     /// Loads a tree from a byte array.
     pub fn from_bytes_mut(bytes: &'a mut [u8]) -> Self {
-        let (allocator, nodes) = bytes.split_at_mut(std::mem::size_of::<Allocator>());
+        let (allocator, nodes) = bytes.split_at_mut(core::mem::size_of::<Allocator>());
 
         let allocator = bytemuck::from_bytes_mut::<Allocator>(allocator);
         let nodes: &mut [Node<K, V>] = bytemuck::cast_slice_mut(nodes);
@@ -249,7 +264,7 @@ impl<
     /// # Arguments
     ///
     /// * `key` - the key of the node.
-    /// • `value` - the value of the node.
+    /// * `value` - the value of the node.
     pub fn insert(&mut self, key: K, value: V) -> Option<u32> {
         let mut reference_node = self.allocator.get_field(Field::Root);
 
@@ -259,7 +274,8 @@ impl<
             return Some(root);
         }
 
-        let mut path: Vec<Ancestor> = Vec::with_capacity((self.len() as f64).log2() as usize);
+        let mut path: Vec<Ancestor> =
+            Vec::with_capacity(self.len().checked_ilog2().unwrap_or(0) as usize);
         path.push((None, None, reference_node));
 
         loop {
@@ -303,7 +319,8 @@ impl<
             return None;
         }
 
-        let mut path: Vec<Ancestor> = Vec::with_capacity((self.len() as f64).log2() as usize);
+        let mut path: Vec<Ancestor> =
+            Vec::with_capacity(self.len().checked_ilog2().unwrap_or(0) as usize);
         path.push((None, None, node_index));
 
         while node_index != SENTINEL {
@@ -335,7 +352,8 @@ impl<
             let mut leftmost = right;
             let mut leftmost_parent = SENTINEL;
             // path to the leftmost descendant
-            let mut inner_path = Vec::with_capacity((self.len() as f64).log2() as usize);
+            let mut inner_path =
+                Vec::with_capacity(self.len().checked_ilog2().unwrap_or(0) as usize);
 
             while node!(self.nodes, leftmost).get_register(Register::Left) != SENTINEL {
                 leftmost_parent = leftmost;
@@ -668,7 +686,7 @@ impl Allocator {
 }
 
 impl Debug for Allocator {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Allocator")
             .field("root", &self.fields[0])
             .field("size", &self.fields[1])
@@ -734,6 +752,8 @@ unsafe impl<
 
 #[cfg(test)]
 mod tests {
+    use std::vec::Vec;
+
     use crate::collections::{avl_tree::Node, AVLTree, AVLTreeMut};
 
     #[test]
@@ -1,12 +1,19 @@
 use bytemuck::{Pod, Zeroable};
-use std::{
-    collections::hash_map::DefaultHasher,
+use core::{
     hash::{Hash, Hasher},
+    marker::PhantomData,
 };
+#[cfg(feature = "std")]
+use std::collections::hash_map::DefaultHasher;
+
+use super::FromBytes;
 
 /// Constant to represent an empty value.
 const SENTINEL: u32 = 0;
 
+/// Constant to mark free nodes while compacting the set.
+const FREE: u32 = u32::MAX;
+
 /// Enum representing the fields of a node.
 #[derive(Copy, Clone)]
 enum Register {
@@ -39,10 +46,15 @@ macro_rules! node {
 /// Macro to implement the readonly interface for a hash set type.
 macro_rules! readonly_impl {
     ( $name:tt ) => {
-        impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> $name<'a, V> {
+        impl<
+                'a,
+                V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable,
+                H: Hasher + Default,
+            > $name<'a, V, H>
+        {
             /// Returns the required data length (in bytes) to store a set with the specified capacity.
             pub const fn data_len(capacity: usize) -> usize {
-                std::mem::size_of::<Allocator>() + (capacity * std::mem::size_of::<Node<V>>())
+                core::mem::size_of::<Allocator>() + (capacity * core::mem::size_of::<Node<V>>())
             }
 
             /// Returns the capacity of the set.
@@ -65,15 +77,45 @@ macro_rules! readonly_impl {
                 self.allocator.get_field(Field::Size) == 0
             }
 
+            /// Returns the ratio between the number of values and the capacity of the set.
+            ///
+            /// Since the number of buckets is the same as the capacity, this is also the
+            /// average length of the bucket chains.
+            pub fn load_factor(&self) -> f32 {
+                match self.capacity() {
+                    0 => 0.0,
+                    capacity => self.size() as f32 / capacity as f32,
+                }
+            }
+
+            /// Returns the number of buckets that hold at least one value.
+            ///
+            /// This scans all buckets, so it runs in O(capacity).
+            pub fn occupied_buckets(&self) -> usize {
+                self.nodes[..self.capacity()]
+                    .iter()
+                    .filter(|node| node.get_register(Register::Bucket) != SENTINEL)
+                    .count()
+            }
+
+            /// Indicates whether the set has free nodes interleaved with nodes in use.
+            ///
+            /// The set uses separate chaining, so removals do not leave tombstones in the
+            /// buckets; instead, the nodes of removed values are kept in a free list to be
+            /// reused. When this returns `true`, [`HashSetMut::compact`] can be used to
+            /// store the values contiguously.
+            pub fn is_fragmented(&self) -> bool {
+                self.allocator.get_field(Field::FreeListHead)
+                    != self.allocator.get_field(Field::Sequence)
+            }
+
             /// Checks whether a value is present in the set or not.
             ///
             /// # Arguments
             ///
             /// * `value` - the value to check.
             pub fn contains(&self, value: &V) -> bool {
-                let mut hasher = DefaultHasher::new();
-                value.hash(&mut hasher);
-                let index = hasher.finish() as u32 % self.allocator.get_field(Field::Capacity);
+                let index = self.bucket(value);
 
                 let head = bucket_node!(self.nodes, index).get_register(Register::Bucket);
                 let mut current = head;
@@ -89,47 +131,124 @@ macro_rules! readonly_impl {
 
                 false
             }
+
+            /// Returns an iterator over the values stored in the bucket at the specified
+            /// index.
+            ///
+            /// The set uses separate chaining, so a bucket holds any number of values. Visiting
+            /// the buckets from `0` to `capacity()` yields every value once, which allows
+            /// processing the set in chunks across multiple calls by resuming from a known
+            /// bucket index, as long as the set is not modified in between. The iterator is
+            /// empty when the bucket has no values or the index is out of bounds.
+            pub fn bucket_at(&self, index: usize) -> impl Iterator<Item = &V> + '_ {
+                let head = if index < self.capacity() {
+                    bucket_node!(self.nodes, index).get_register(Register::Bucket)
+                } else {
+                    SENTINEL
+                };
+
+                let node = |index: u32| Some(index).filter(|index| *index != SENTINEL);
+
+                core::iter::successors(node(head), move |current| {
+                    node(node!(self.nodes, *current).get_register(Register::Next))
+                })
+                .map(|current| &node!(self.nodes, current).value)
+            }
+
+            /// An iterator visiting all elements in arbitrary order.
+            /// The iterator element type is `&'a V`.
+            pub fn iter(&self) -> HashSetIterator<'_, V> {
+                HashSetIterator::<V> {
+                    nodes: self.nodes,
+                    capacity: self.allocator.get_field(Field::Capacity),
+                    bucket: SENTINEL,
+                    node: SENTINEL,
+                }
+            }
+
+            /// Returns the bucket index of a value.
+            fn bucket(&self, value: &V) -> u32 {
+                let mut hasher = H::default();
+                value.hash(&mut hasher);
+                hasher.finish() as u32 % self.allocator.get_field(Field::Capacity)
+            }
         }
     };
 }
 
 /// Simple `HashSet` implementation where values are stored in a contiguous array.
 ///
+/// Values are assigned to buckets using the hasher `H`, which defaults to the
+/// standard library `DefaultHasher` when the `std` feature is enabled; without it,
+/// the hasher must be specified. A cheaper hasher can be used when the values
+/// are already uniformly distributed; the same hasher must be used to read and
+/// write a set.
+///
 /// This type can be used to reference a read-only set.
-pub struct HashSet<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> {
+pub struct HashSet<
+    'a,
+    V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable,
+    #[cfg(feature = "std")] H: Hasher + Default = DefaultHasher,
+    #[cfg(not(feature = "std"))] H: Hasher + Default,
+> {
     /// Node allocator.
     allocator: &'a Allocator,
 
     /// Array to store the values.
     nodes: &'a [Node<V>],
+
+    /// Hasher that determines the bucket of the values.
+    hasher: PhantomData<fn() -> H>,
+}
+
+// manual implementations to avoid requiring the hasher to be `Clone` and `Copy`
+impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable, H: Hasher + Default> Clone
+    for HashSet<'a, V, H>
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable, H: Hasher + Default> Copy
+    for HashSet<'a, V, H>
+{
 }
 
 readonly_impl!(HashSet);
 
-impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> HashSet<'a, V> {
+impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable, H: Hasher + Default>
+    HashSet<'a, V, H>
+{
     /// Loads a set from a byte array.
     pub fn from_bytes(bytes: &'a [u8]) -> Self {
-        let (allocator, nodes) = bytes.split_at(std::mem::size_of::<Allocator>());
+        let (allocator, nodes) = bytes.split_at(core::mem::size_of::<Allocator>());
 
         let allocator = bytemuck::from_bytes::<Allocator>(allocator);
         let nodes = bytemuck::cast_slice(nodes);
 
-        Self { allocator, nodes }
+        Self {
+            allocator,
+            nodes,
+            hasher: PhantomData,
+        }
     }
+}
 
-    /// An iterator visiting all elements in arbitrary order.
-    /// The iterator element type is `&'a V`.
-    pub fn iter(&self) -> HashSetIterator<'_, V> {
-        HashSetIterator::<V> {
-            hash_set: self,
-            bucket: SENTINEL,
-            node: SENTINEL,
-        }
+impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable, H: Hasher + Default>
+    FromBytes<'a> for HashSet<'a, V, H>
+{
+    fn from_bytes(bytes: &'a [u8]) -> Self {
+        HashSet::from_bytes(bytes)
     }
 }
 
+/// Iterator over the values of a set.
 pub struct HashSetIterator<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> {
-    hash_set: &'a HashSet<'a, V>,
+    /// Array of nodes of the set.
+    nodes: &'a [Node<V>],
+    /// Number of buckets of the set.
+    capacity: u32,
     bucket: u32,
     node: u32,
 }
@@ -140,15 +259,15 @@ impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> Iterator
     type Item = &'a V;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.bucket <= self.hash_set.capacity() as u32 {
+        if self.bucket <= self.capacity {
             while self.node == SENTINEL {
                 self.bucket += 1;
-                if self.bucket > self.hash_set.capacity() as u32 {
+                if self.bucket > self.capacity {
                     return None;
                 }
-                self.node = node!(self.hash_set.nodes, self.bucket).get_register(Register::Bucket);
+                self.node = node!(self.nodes, self.bucket).get_register(Register::Bucket);
             }
-            let node = &node!(self.hash_set.nodes, self.node);
+            let node = &node!(self.nodes, self.node);
             self.node = node.get_register(Register::Next);
             Some(&node.value)
         } else {
@@ -159,26 +278,42 @@ impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> Iterator
 
 /// Simple `HashSet` implementation where values are stored in a contiguous array.
 ///
+/// See [`HashSet`] for details on the hasher `H`.
+///
 /// This type can be used to reference a mutable set.
-pub struct HashSetMut<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> {
+pub struct HashSetMut<
+    'a,
+    V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable,
+    #[cfg(feature = "std")] H: Hasher + Default = DefaultHasher,
+    #[cfg(not(feature = "std"))] H: Hasher + Default,
+> {
     /// Node allocator.
     allocator: &'a mut Allocator,
 
     /// Array to store the values.
     nodes: &'a mut [Node<V>],
+
+    /// Hasher that determines the bucket of the values.
+    hasher: PhantomData<fn() -> H>,
 }
 
 readonly_impl!(HashSetMut);
 
-impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> HashSetMut<'a, V> {
+impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable, H: Hasher + Default>
+    HashSetMut<'a, V, H>
+{
     /// Loads a set from a byte array.
     pub fn from_bytes_mut(bytes: &'a mut [u8]) -> Self {
-        let (allocator, nodes) = bytes.split_at_mut(std::mem::size_of::<Allocator>());
+        let (allocator, nodes) = bytes.split_at_mut(core::mem::size_of::<Allocator>());
 
         let allocator = bytemuck::from_bytes_mut::<Allocator>(allocator);
         let nodes = bytemuck::cast_slice_mut(nodes);
 
-        Self { allocator, nodes }
+        Self {
+            allocator,
+            nodes,
+            hasher: PhantomData,
+        }
     }
 
     /// Initializes the set with the specified capacity.
@@ -203,9 +338,7 @@ impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> HashSetM
             return false;
         }
 
-        let mut hasher = DefaultHasher::new();
-        value.hash(&mut hasher);
-        let index = hasher.finish() as u32 % self.allocator.get_field(Field::Capacity);
+        let index = self.bucket(&value);
 
         let head = bucket_node!(self.nodes, index).get_register(Register::Bucket);
         let mut current = head;
@@ -240,9 +373,7 @@ impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> HashSetM
             return false;
         }
 
-        let mut hasher = DefaultHasher::new();
-        value.hash(&mut hasher);
-        let index = hasher.finish() as u32 % self.allocator.get_field(Field::Capacity);
+        let index = self.bucket(value);
 
         let head = bucket_node!(self.nodes, index).get_register(Register::Bucket);
         let mut current = head;
@@ -254,7 +385,9 @@ impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> HashSetM
             // it again
             if &node.value == value {
                 if previous == SENTINEL {
-                    bucket_node!(self.nodes, index).set_register(Register::Bucket, SENTINEL);
+                    // the next node (if any) becomes the head of the bucket
+                    bucket_node!(self.nodes, index)
+                        .set_register(Register::Bucket, node.get_register(Register::Next));
                 } else {
                     node!(self.nodes, previous)
                         .set_register(Register::Next, node.get_register(Register::Next));
@@ -270,6 +403,64 @@ impl<'a, V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> HashSetM
         false
     }
 
+    /// Rebuilds the set so that values are stored contiguously.
+    ///
+    /// The set uses separate chaining, so removals do not leave tombstones; however,
+    /// after many removals the values in use are interleaved with free nodes. This
+    /// moves the values to the start of the node storage, rebuilds the bucket chains
+    /// and resets the free list. It is useful to call it after removing a large number
+    /// of values, since it improves the locality of lookups and iteration.
+    ///
+    /// The values are rearranged in place, so this does not require any allocation.
+    pub fn compact(&mut self) {
+        let capacity = self.allocator.get_field(Field::Capacity);
+        let size = self.allocator.get_field(Field::Size);
+        let sequence = self.allocator.get_field(Field::Sequence);
+        // marks the free nodes: the ones in the free list (which ends at the
+        // sequence) and the ones that were never used
+        let mut free = self.allocator.get_field(Field::FreeListHead);
+
+        while free != sequence {
+            let node = &mut node!(self.nodes, free);
+            free = node.get_register(Register::Next);
+            node.set_register(Register::Next, FREE);
+        }
+
+        for index in sequence..=capacity {
+            node!(self.nodes, index).set_register(Register::Next, FREE);
+        }
+        // moves the values to the start of the storage
+        let mut position = 1;
+
+        for index in 1..=capacity {
+            if node!(self.nodes, index).get_register(Register::Next) != FREE {
+                node!(self.nodes, position).value = node!(self.nodes, index).value;
+                position += 1;
+            }
+        }
+        // clears the buckets and free nodes
+        for index in 1..=capacity {
+            let node = &mut node!(self.nodes, index);
+            node.set_register(Register::Bucket, SENTINEL);
+            node.set_register(Register::Next, SENTINEL);
+
+            if index > size {
+                node.value = V::default();
+            }
+        }
+        // rebuilds the bucket chains
+        for index in 1..=size {
+            let bucket = self.bucket(&node!(self.nodes, index).value);
+            let head = bucket_node!(self.nodes, bucket).get_register(Register::Bucket);
+
+            node!(self.nodes, index).set_register(Register::Next, head);
+            bucket_node!(self.nodes, bucket).set_register(Register::Bucket, index);
+        }
+        // free nodes follow the values
+        self.allocator.set_field(Field::FreeListHead, size + 1);
+        self.allocator.set_field(Field::Sequence, size + 1);
+    }
+
     /// Adds a node to the set.
     ///
     /// The node is only added if there is space on the nodes' array. The index
@@ -406,7 +597,39 @@ unsafe impl<V: Default + Copy + Clone + Hash + PartialEq + Pod + Zeroable> Pod f
 
 #[cfg(test)]
 mod tests {
-    use crate::collections::HashSetMut;
+    use core::hash::Hasher;
+    use std::{vec, vec::Vec};
+
+    #[cfg(feature = "std")]
+    use crate::collections::{HashSet, HashSetMut};
+
+    // without the `std` feature, the sets have no default hasher
+    #[cfg(not(feature = "std"))]
+    type HashSet<'a, V, H = std::collections::hash_map::DefaultHasher> =
+        crate::collections::HashSet<'a, V, H>;
+    #[cfg(not(feature = "std"))]
+    type HashSetMut<'a, V, H = std::collections::hash_map::DefaultHasher> =
+        crate::collections::HashSetMut<'a, V, H>;
+
+    /// Hasher that uses the value itself as the hash.
+    #[derive(Default)]
+    struct IdentityHasher(u64);
+
+    impl Hasher for IdentityHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 = (self.0 << 8) | *byte as u64;
+            }
+        }
+
+        fn write_u64(&mut self, value: u64) {
+            self.0 = value;
+        }
+    }
 
     #[test]
     fn test_insert() {
@@ -516,6 +739,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_remove_chain_head() {
+        const CAPACITY: usize = 100;
+
+        let mut data = [0u8; HashSetMut::<u64>::data_len(CAPACITY)];
+        let mut set = HashSetMut::<u64>::from_bytes_mut(&mut data);
+
+        set.allocator.initialize(CAPACITY as u32);
+
+        for value in 1..=CAPACITY as u64 {
+            assert!(set.insert(value));
+        }
+
+        // values are added to the head of their bucket chain, so removing
+        // them in reverse order always removes the head of a chain
+        for value in (1..=CAPACITY as u64).rev() {
+            assert!(set.remove(&value));
+            assert_eq!(set.size(), value as usize - 1);
+
+            for remaining in 1..value {
+                assert!(set.contains(&remaining));
+            }
+        }
+    }
+
     #[test]
     fn test_insert_when_full() {
         const CAPACITY: usize = 10;
@@ -546,4 +794,214 @@ mod tests {
         assert!(set.is_full());
         assert!(!set.insert(20));
     }
+
+    #[test]
+    fn test_iter() {
+        const CAPACITY: usize = 100;
+
+        let mut data = vec![0u8; HashSetMut::<u64>::data_len(CAPACITY)];
+        let mut set = HashSetMut::<u64>::from_bytes_mut(&mut data);
+        set.initialize(CAPACITY as u32);
+
+        assert_eq!(set.iter().count(), 0);
+
+        for value in 1..=CAPACITY as u64 {
+            assert!(set.insert(value));
+        }
+        // removes values, including the head of bucket chains
+        for value in (1..=CAPACITY as u64).filter(|value| value % 3 == 0) {
+            assert!(set.remove(&value));
+        }
+
+        let expected: std::collections::HashSet<u64> = (1..=CAPACITY as u64)
+            .filter(|value| value % 3 != 0)
+            .collect();
+
+        let values: std::collections::HashSet<u64> = set.iter().copied().collect();
+        assert_eq!(values, expected);
+        assert_eq!(set.iter().count(), set.size());
+
+        for value in &expected {
+            assert!(set.contains(value));
+        }
+
+        let set = HashSet::<u64>::from_bytes(&data);
+        let values: std::collections::HashSet<u64> = set.iter().copied().collect();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn test_compact() {
+        const CAPACITY: usize = 1_000;
+
+        let mut data = vec![0u8; HashSetMut::<u64>::data_len(CAPACITY)];
+        let mut set = HashSetMut::<u64>::from_bytes_mut(&mut data);
+        set.initialize(CAPACITY as u32);
+
+        for value in 1..=800u64 {
+            assert!(set.insert(value));
+        }
+        // removes most of the values
+        for value in (1..=800u64).filter(|value| value % 4 != 0) {
+            assert!(set.remove(&value));
+        }
+        assert_eq!(set.size(), 200);
+
+        set.compact();
+
+        assert_eq!(set.size(), 200);
+        assert_eq!(set.iter().count(), 200);
+
+        for value in 1..=800u64 {
+            assert_eq!(set.contains(&value), value % 4 == 0);
+        }
+        // values are stored contiguously
+        assert!(set.nodes[..200].iter().all(|node| node.value % 4 == 0));
+        assert!(set.nodes[200..].iter().all(|node| node.value == 0));
+
+        // the set can be filled after compacting
+        for value in 1001..=1800u64 {
+            assert!(set.insert(value));
+        }
+        assert!(set.is_full());
+        assert!(set.contains(&4) && set.contains(&1800));
+    }
+
+    #[test]
+    fn test_occupancy() {
+        const CAPACITY: usize = 100;
+
+        let mut data = vec![0u8; HashSetMut::<u64>::data_len(CAPACITY)];
+        let mut set = HashSetMut::<u64>::from_bytes_mut(&mut data);
+        set.initialize(CAPACITY as u32);
+
+        assert_eq!(set.load_factor(), 0.0);
+        assert_eq!(set.occupied_buckets(), 0);
+
+        for value in 1..=50u64 {
+            assert!(set.insert(value));
+        }
+        assert_eq!(set.load_factor(), 0.5);
+        assert!(!set.is_fragmented());
+
+        let occupied = set.occupied_buckets();
+        assert!(occupied > 0 && occupied <= 50);
+
+        for value in (1..=50u64).filter(|value| value % 2 == 0) {
+            assert!(set.remove(&value));
+        }
+        assert_eq!(set.load_factor(), 0.25);
+        assert!(set.is_fragmented());
+        assert!(set.occupied_buckets() <= occupied);
+
+        set.compact();
+        assert!(!set.is_fragmented());
+
+        let set = HashSet::<u64>::from_bytes(&data);
+        assert_eq!(set.load_factor(), 0.25);
+        assert!(set.occupied_buckets() <= 25);
+    }
+
+    #[test]
+    fn test_bucket_at() {
+        const CAPACITY: usize = 20;
+
+        let mut data = vec![0u8; HashSetMut::<u64>::data_len(CAPACITY)];
+        let mut set = HashSetMut::<u64>::from_bytes_mut(&mut data);
+        set.initialize(CAPACITY as u32);
+
+        for value in 1..=15u64 {
+            assert!(set.insert(value));
+        }
+        assert!(set.remove(&7));
+
+        let set = HashSet::<u64>::from_bytes(&data);
+        // visits the buckets in two chunks
+        let mut values: Vec<u64> = (0..10)
+            .flat_map(|index| set.bucket_at(index))
+            .copied()
+            .collect();
+        values.extend((10..set.capacity()).flat_map(|index| set.bucket_at(index)));
+
+        values.sort();
+        let expected: Vec<u64> = (1..=15u64).filter(|value| *value != 7).collect();
+        assert_eq!(values, expected);
+
+        assert_eq!(set.bucket_at(CAPACITY).count(), 0);
+    }
+
+    #[test]
+    fn test_custom_hasher() {
+        const CAPACITY: usize = 10;
+
+        let mut data = vec![0u8; HashSetMut::<u64, IdentityHasher>::data_len(CAPACITY)];
+        let mut set = HashSetMut::<u64, IdentityHasher>::from_bytes_mut(&mut data);
+        set.initialize(CAPACITY as u32);
+
+        for value in [3, 13, 23, 4, 7] {
+            assert!(set.insert(value));
+        }
+        assert!(!set.insert(13));
+
+        // values are assigned to buckets by their value
+        let mut bucket: Vec<u64> = set.bucket_at(3).copied().collect();
+        bucket.sort();
+        assert_eq!(bucket, [3, 13, 23]);
+        assert_eq!(set.occupied_buckets(), 3);
+
+        assert!(set.remove(&13));
+
+        let set = HashSet::<u64, IdentityHasher>::from_bytes(&data);
+        assert_eq!(set.size(), 4);
+
+        for value in [3, 23, 4, 7] {
+            assert!(set.contains(&value));
+        }
+        assert!(!set.contains(&13));
+        assert!(!set.contains(&5));
+    }
+
+    /// FNV-1a hasher, which does not depend on `std`.
+    struct FnvHasher(u64);
+
+    impl Default for FnvHasher {
+        fn default() -> Self {
+            Self(0xcbf2_9ce4_8422_2325)
+        }
+    }
+
+    impl Hasher for FnvHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fnv_hasher() {
+        const CAPACITY: usize = 20;
+
+        let mut data = [0u8; HashSetMut::<u32, FnvHasher>::data_len(CAPACITY)];
+        let mut set = HashSetMut::<u32, FnvHasher>::from_bytes_mut(&mut data);
+        set.initialize(CAPACITY as u32);
+
+        for value in 0..CAPACITY as u32 {
+            assert!(set.insert(value * 3));
+        }
+        assert!(set.is_full());
+        assert!(set.remove(&9));
+
+        let set = HashSet::<u32, FnvHasher>::from_bytes(&data);
+        assert_eq!(set.size(), CAPACITY - 1);
+
+        for value in 0..CAPACITY as u32 {
+            assert_eq!(set.contains(&(value * 3)), value != 3);
+        }
+        assert!(!set.contains(&1));
+    }
 }
@@ -1,12 +1,67 @@
+pub mod array_map;
 pub mod array_set;
+#[cfg(feature = "alloc")]
 pub mod avl_tree;
 pub mod hash_set;
 pub mod u8_avl_tree;
 
+pub use array_map::*;
 pub use array_set::*;
+#[cfg(feature = "alloc")]
 pub use avl_tree::AVLTree;
+#[cfg(feature = "alloc")]
 pub use avl_tree::AVLTreeMut;
 pub use hash_set::HashSet;
 pub use hash_set::HashSetMut;
 pub use u8_avl_tree::U8AVLTree;
 pub use u8_avl_tree::U8AVLTreeMut;
+
+/// Trait for read-only collections that can be loaded from their byte representation.
+///
+/// This allows generic code to load any of the collections without knowing its
+/// concrete type.
+pub trait FromBytes<'a>: Sized {
+    /// Loads the collection from its byte representation.
+    fn from_bytes(bytes: &'a [u8]) -> Self;
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::collections::{
+        FromBytes, HashSet, HashSetMut, U32ArraySet, U32ArraySetMut, U8AVLTree, U8AVLTreeMut,
+    };
+
+    fn assert_loads<'a, T: FromBytes<'a>>(bytes: &'a [u8], check: impl Fn(&T) -> bool) {
+        let collection = T::from_bytes(bytes);
+        assert!(check(&collection));
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        const CAPACITY: usize = 10;
+
+        let mut data = vec![0u8; U32ArraySetMut::<u32>::data_len(CAPACITY)];
+        let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut data);
+        set.insert(1);
+        set.insert(2);
+        assert_loads(&data, |set: &U32ArraySet<u32>| {
+            set.len() == 2 && set.contains(&1) && set.contains(&2)
+        });
+
+        let mut data = vec![0u8; U8AVLTreeMut::<u32, u32>::data_len(CAPACITY)];
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+        tree.insert(1, 10);
+        assert_loads(&data, |tree: &U8AVLTree<u32, u32>| {
+            tree.len() == 1 && tree.get(&1) == Some(10)
+        });
+
+        let mut data = vec![0u8; HashSetMut::<u64>::data_len(CAPACITY)];
+        let mut set = HashSetMut::<u64>::from_bytes_mut(&mut data);
+        set.initialize(CAPACITY as u32);
+        set.insert(7);
+        assert_loads(&data, |set: &HashSet<u64>| {
+            set.contains(&7) && !set.contains(&8)
+        });
+    }
+}
@@ -1,5 +1,16 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use bytemuck::{Pod, Zeroable};
-use std::{cmp::max, fmt::Debug};
+use core::{
+    borrow::Borrow,
+    cmp::{max, Ordering},
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use super::FromBytes;
+use crate::{cast, TranslationError};
 
 /// Constant to represent an empty value.
 const SENTINEL: u8 = 0;
@@ -10,6 +21,7 @@ enum Register {
     Left,
     Right,
     Height,
+    Size,
 }
 
 /// Enum representing the fields of the allocator.
@@ -21,10 +33,66 @@ enum Field {
     Sequence,
 }
 
+/// Maximum number of nodes of a tree.
+///
+/// Node indices are stored as `u8` values, where `0` is reserved for the sentinel
+/// and the allocator sequence needs to be able to reach `capacity + 1`.
+const MAX_CAPACITY: usize = u8::MAX as usize - 1;
+
 /// Type representing a path entry (parent, branch, child) when
 /// traversing the tree.
 type Ancestor = (Option<u8>, Option<Register>, u8);
 
+/// Maximum number of entries in a path when traversing the tree.
+///
+/// The smallest AVL tree with height 11 has 376 nodes, so a tree indexed by `u8`
+/// has a height of at most 10 and a path from the root to a leaf has at most 11
+/// entries. The extra entry is a safety margin.
+const MAX_PATH_LENGTH: usize = 12;
+
+/// Stack-allocated path used when traversing the tree.
+///
+/// The height of the tree is bounded by the `u8` index, so the path can be
+/// stored in a fixed-size array instead of requiring a heap allocation.
+struct Path {
+    /// Entries of the path.
+    entries: [Ancestor; MAX_PATH_LENGTH],
+
+    /// Number of entries in use.
+    length: usize,
+}
+
+impl Path {
+    #[inline(always)]
+    fn new() -> Self {
+        Self {
+            entries: [(None, None, SENTINEL); MAX_PATH_LENGTH],
+            length: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn push(&mut self, ancestor: Ancestor) {
+        self.entries[self.length] = ancestor;
+        self.length += 1;
+    }
+
+    #[inline(always)]
+    fn pop(&mut self) -> Option<Ancestor> {
+        if self.length == 0 {
+            None
+        } else {
+            self.length -= 1;
+            Some(self.entries[self.length])
+        }
+    }
+
+    #[inline(always)]
+    fn as_slice(&self) -> &[Ancestor] {
+        &self.entries[..self.length]
+    }
+}
+
 /// Macro to access a node.
 macro_rules! node {
     ( $array:expr, $index:expr ) => {
@@ -43,8 +111,26 @@ macro_rules! readonly_impl {
         {
             /// Returns the required data length (in bytes) to store a tree with the specified capacity.
             pub const fn data_len(capacity: usize) -> usize {
-                std::mem::size_of::<U8Allocator>()
-                    + (capacity * std::mem::size_of::<U8Node<K, V>>())
+                core::mem::size_of::<U8Allocator>()
+                    + (capacity * core::mem::size_of::<U8Node<K, V>>())
+            }
+
+            /// Returns a copy of the bytes of the tree, i.e., the allocator and the nodes
+            /// up to the capacity of the tree.
+            ///
+            /// The bytes can be loaded as a tree with the same entries and capacity.
+            #[cfg(feature = "alloc")]
+            pub fn to_owned_bytes(&self) -> Vec<u8> {
+                let capacity = self.capacity();
+                let mut bytes = Vec::with_capacity(Self::data_len(capacity));
+                bytes.extend_from_slice(bytemuck::bytes_of(&*self.allocator));
+                bytes.extend_from_slice(bytemuck::cast_slice(&self.nodes[..capacity]));
+                bytes
+            }
+
+            /// Returns the maximum capacity that a tree can have.
+            pub const fn max_capacity() -> usize {
+                MAX_CAPACITY
             }
 
             /// Returns the capacity of the tree.
@@ -52,6 +138,18 @@ macro_rules! readonly_impl {
                 self.allocator.get_field(Field::Capacity) as usize
             }
 
+            /// Returns the number of bytes used by the tree.
+            ///
+            /// Nodes are allocated up to the capacity of the tree, so this includes free
+            /// nodes and is the same as `data_len(capacity())`. Any bytes after it (e.g.,
+            /// when the bytes have been resized but not loaded mutably) are not part of the
+            /// tree. Note that the tree cannot be truncated to its length: even after a
+            /// [`compact`](U8AVLTreeMut::compact), the capacity stored in the allocator
+            /// would exceed the number of nodes.
+            pub fn used_len(&self) -> usize {
+                Self::data_len(self.capacity())
+            }
+
             /// Returns the number of nodes in the tree.
             pub fn len(&self) -> usize {
                 self.allocator.get_field(Field::Size) as usize
@@ -62,6 +160,22 @@ macro_rules! readonly_impl {
                 self.allocator.get_field(Field::Size) >= self.allocator.get_field(Field::Capacity)
             }
 
+            /// Returns the number of free nodes of the tree.
+            pub fn free_count(&self) -> usize {
+                // the size of a malformed tree can exceed its capacity
+                self.capacity().saturating_sub(self.len())
+            }
+
+            /// Indicates whether the tree has free nodes interleaved with nodes in use.
+            ///
+            /// This is the case when nodes were removed and their slots are in the free
+            /// list waiting to be reused, instead of free nodes being only at the end of
+            /// the node storage.
+            pub fn is_fragmented(&self) -> bool {
+                self.allocator.get_field(Field::FreeListHead)
+                    != self.allocator.get_field(Field::Sequence)
+            }
+
             /// Indicates whether the tree is empty or not.
             pub fn is_empty(&self) -> bool {
                 self.allocator.get_field(Field::Size) == 0
@@ -77,19 +191,261 @@ macro_rules! readonly_impl {
                     .map(|node_index| node!(self.nodes, node_index).value)
             }
 
+            /// Returns an iterator over the values under the specified keys, yielding
+            /// `Some` or `None` for each key in input order.
+            ///
+            /// The keys must be sorted in ascending order (duplicates are allowed); the
+            /// lookup is a single in-order traversal of the tree merged against the keys,
+            /// which runs in O(n + m) instead of O(m log n) for individual lookups. If the
+            /// keys are not sorted, the result is unspecified.
+            ///
+            /// # Arguments
+            ///
+            /// * `keys` - sorted keys to look up the values.
+            pub fn get_sorted<'s>(&'s self, keys: &'s [K]) -> impl Iterator<Item = Option<V>> + 's {
+                let mut entries = self.iter().peekable();
+
+                keys.iter().map(move |key| {
+                    while entries.next_if(|(k, _)| *k < key).is_some() {}
+
+                    entries
+                        .peek()
+                        .filter(|(k, _)| *k == key)
+                        .map(|(_, value)| **value)
+                })
+            }
+
+            /// Return the value under the specified key, if one is found, using a borrowed
+            /// form of the key.
+            ///
+            /// This allows looking up keys without creating a `K`, e.g., using a `&[u8]`
+            /// for `[u8; N]` keys. The order on the borrowed form must match the order on
+            /// the key type.
+            ///
+            /// # Arguments
+            ///
+            /// * `key` - borrowed form of the key to look up the value.
+            pub fn get_borrowed<Q: PartialOrd + ?Sized>(&self, key: &Q) -> Option<V>
+            where
+                K: Borrow<Q>,
+            {
+                self.find_borrowed(key)
+                    .map(|node_index| node!(self.nodes, node_index).value)
+            }
+
+            /// Checks whether a key is present in the tree or not, using a borrowed form
+            /// of the key.
+            ///
+            /// See [`Self::get_borrowed`] for the requirements on the borrowed form.
+            pub fn contains_borrowed<Q: PartialOrd + ?Sized>(&self, key: &Q) -> bool
+            where
+                K: Borrow<Q>,
+            {
+                self.find_borrowed(key).is_some()
+            }
+
+            /// Return the value under the specified key or the default value when the
+            /// key is not found.
+            ///
+            /// # Arguments
+            ///
+            /// * `key` - key to look up the value.
+            pub fn get_or_default(&self, key: &K) -> V {
+                self.get(key).unwrap_or_default()
+            }
+
+            /// Returns an iterator over the values under the specified keys.
+            ///
+            /// The iterator yields one item for each key, in the same order, which is
+            /// `None` when the key is not found.
+            pub fn get_many<'k>(&'k self, keys: &'k [K]) -> impl Iterator<Item = Option<V>> + 'k {
+                keys.iter().map(move |key| self.get(key))
+            }
+
             // Find the lowest entry.
             pub fn lowest(&self) -> Option<K> {
-                let mut node = self.allocator.get_field(Field::Root);
+                self.min_key()
+            }
 
-                if node == SENTINEL {
+            /// Returns the lowest key of the tree, if any.
+            ///
+            /// The key is returned by value, so it does not borrow the tree.
+            pub fn min_key(&self) -> Option<K> {
+                self.leftmost().map(|node| node!(self.nodes, node).key)
+            }
+
+            /// Returns the highest key of the tree, if any.
+            ///
+            /// The key is returned by value, so it does not borrow the tree.
+            pub fn max_key(&self) -> Option<K> {
+                self.rightmost().map(|node| node!(self.nodes, node).key)
+            }
+
+            /// Returns the entry with the lowest key, if any.
+            pub fn first_key_value(&self) -> Option<(&K, &V)> {
+                self.leftmost().map(|node| {
+                    let node = &node!(self.nodes, node);
+                    (&node.key, &node.value)
+                })
+            }
+
+            /// Returns the entry with the highest key, if any.
+            pub fn last_key_value(&self) -> Option<(&K, &V)> {
+                self.rightmost().map(|node| {
+                    let node = &node!(self.nodes, node);
+                    (&node.key, &node.value)
+                })
+            }
+
+            /// Returns the entry with the greatest key that is less than or equal to the
+            /// specified key, if any.
+            ///
+            /// # Arguments
+            ///
+            /// * `key` - key to look up the entry.
+            pub fn floor(&self, key: &K) -> Option<(&K, &V)> {
+                self.bound(key, true)
+            }
+
+            /// Returns the entry with the least key that is greater than or equal to the
+            /// specified key, if any.
+            ///
+            /// # Arguments
+            ///
+            /// * `key` - key to look up the entry.
+            pub fn ceil(&self, key: &K) -> Option<(&K, &V)> {
+                self.bound(key, false)
+            }
+
+            /// Finds the floor (or ceiling) entry of a key.
+            ///
+            /// The best candidate is tracked while descending the tree: when looking for
+            /// the floor, every node with a key lower than the specified key is a candidate
+            /// and the search continues on its right subtree (and the opposite for the
+            /// ceiling). A node with the same key is returned right away.
+            fn bound(&self, key: &K, floor: bool) -> Option<(&K, &V)> {
+                let mut candidate = SENTINEL;
+                let mut reference_node = self.allocator.get_field(Field::Root);
+
+                while reference_node != SENTINEL {
+                    let node = &node!(self.nodes, reference_node);
+
+                    reference_node = if *key < node.key {
+                        if !floor {
+                            candidate = reference_node;
+                        }
+                        node.get_register(Register::Left)
+                    } else if *key > node.key {
+                        if floor {
+                            candidate = reference_node;
+                        }
+                        node.get_register(Register::Right)
+                    } else {
+                        candidate = reference_node;
+                        break;
+                    };
+                }
+
+                (candidate != SENTINEL).then(|| {
+                    let node = &node!(self.nodes, candidate);
+                    (&node.key, &node.value)
+                })
+            }
+
+            /// Returns an iterator over the entries of the tree, sorted by key.
+            pub fn iter(&self) -> Iter<'_, K, V> {
+                Iter::new(self.nodes, self.allocator.get_field(Field::Root), false)
+            }
+
+            /// Returns an iterator over the entries of the tree, sorted by key in
+            /// descending order.
+            pub fn iter_rev(&self) -> Iter<'_, K, V> {
+                Iter::new(self.nodes, self.allocator.get_field(Field::Root), true)
+            }
+
+            /// Returns the entry with the greatest value with respect to the specified
+            /// comparison function, if any.
+            ///
+            /// If several entries are equally maximum, the one with the highest key is
+            /// returned. Values are not ordered in the tree, so this visits every entry
+            /// and runs in O(n).
+            pub fn max_by_value<F: Fn(&V, &V) -> Ordering>(&self, f: F) -> Option<(&K, &V)> {
+                self.iter().max_by(|a, b| f(a.1, b.1))
+            }
+
+            /// Returns the entry with the smallest value with respect to the specified
+            /// comparison function, if any.
+            ///
+            /// If several entries are equally minimum, the one with the lowest key is
+            /// returned. Values are not ordered in the tree, so this visits every entry
+            /// and runs in O(n).
+            pub fn min_by_value<F: Fn(&V, &V) -> Ordering>(&self, f: F) -> Option<(&K, &V)> {
+                self.iter().min_by(|a, b| f(a.1, b.1))
+            }
+
+            /// Returns the entry at the specified position in key order, if any.
+            ///
+            /// The position is zero-based, so `select(0)` returns the entry with the
+            /// lowest key. This uses the subtree sizes stored in the nodes, so it
+            /// runs in O(log n).
+            ///
+            /// Trees written before subtree sizes were stored have a size of `0` in
+            /// their nodes, in which case `None` is returned. Calling
+            /// [`U8AVLTreeMut::compact`] once rebuilds the sizes of such trees.
+            pub fn select(&self, position: usize) -> Option<(&K, &V)> {
+                if !self.has_sizes() {
+                    return None;
+                }
+
+                let mut node_index = self.allocator.get_field(Field::Root);
+                let mut position = position;
+
+                while node_index != SENTINEL {
+                    let node = &node!(self.nodes, node_index);
+                    let left = self.size(node.get_register(Register::Left)) as usize;
+
+                    node_index = match position.cmp(&left) {
+                        Ordering::Less => node.get_register(Register::Left),
+                        Ordering::Equal => return Some((&node.key, &node.value)),
+                        Ordering::Greater => {
+                            position -= left + 1;
+                            node.get_register(Register::Right)
+                        }
+                    };
+                }
+
+                None
+            }
+
+            /// Returns the position of the key in key order (i.e., the number of keys
+            /// lower than it), if the key is present in the tree.
+            ///
+            /// This uses the subtree sizes stored in the nodes, so it runs in O(log n).
+            /// As with [`Self::select`], `None` is returned for trees written before
+            /// subtree sizes were stored.
+            pub fn rank(&self, key: &K) -> Option<usize> {
+                if !self.has_sizes() {
                     return None;
                 }
 
-                while node!(self.nodes, node).get_register(Register::Left) != SENTINEL {
-                    node = node!(self.nodes, node).get_register(Register::Left);
+                let mut node_index = self.allocator.get_field(Field::Root);
+                let mut rank = 0;
+
+                while node_index != SENTINEL {
+                    let node = &node!(self.nodes, node_index);
+                    let left = node.get_register(Register::Left);
+
+                    node_index = if *key < node.key {
+                        left
+                    } else if *key > node.key {
+                        rank += self.size(left) as usize + 1;
+                        node.get_register(Register::Right)
+                    } else {
+                        return Some(rank + self.size(left) as usize);
+                    };
                 }
 
-                Some(node!(self.nodes, node).key)
+                None
             }
 
             /// Checks whether a key is present in the tree or not.
@@ -101,15 +457,117 @@ macro_rules! readonly_impl {
                 self.find(key).is_some()
             }
 
+            /// Returns the height of the tree, i.e., the number of nodes on the longest
+            /// path from the root to a leaf.
+            ///
+            /// An empty tree has height `0` and a tree with a single node has height `1`.
+            pub fn height(&self) -> usize {
+                match self.allocator.get_field(Field::Root) {
+                    SENTINEL => 0,
+                    root => node!(self.nodes, root).get_register(Register::Height) as usize + 1,
+                }
+            }
+
+            /// Indicates whether the tree satisfies the AVL invariant.
+            ///
+            /// This checks that the heights of the subtrees of every node differ by one at
+            /// most and that the height stored on each node matches its subtree. It visits
+            /// every node, so it is meant for tests and debugging.
+            pub fn is_balanced(&self) -> bool {
+                self.checked_height(self.allocator.get_field(Field::Root), 0)
+                    .is_some()
+            }
+
+            /// Returns the height of the subtree rooted at the specified node, or `None`
+            /// if any node of the subtree is not balanced or has an incorrect height.
+            ///
+            /// The `depth` of the node bounds the recursion, so a malformed tree with a
+            /// cycle is reported as not balanced.
+            fn checked_height(&self, index: u8, depth: usize) -> Option<usize> {
+                if index == SENTINEL {
+                    return Some(0);
+                }
+
+                if depth >= MAX_PATH_LENGTH {
+                    return None;
+                }
+
+                let node = &node!(self.nodes, index);
+                let left = self.checked_height(node.get_register(Register::Left), depth + 1)?;
+                let right = self.checked_height(node.get_register(Register::Right), depth + 1)?;
+                let height = max(left, right) + 1;
+
+                if left.abs_diff(right) > 1
+                    || node.get_register(Register::Height) as usize + 1 != height
+                {
+                    return None;
+                }
+
+                Some(height)
+            }
+
+            /// Returns the index of the leftmost node (lowest key) of the tree.
+            fn leftmost(&self) -> Option<u8> {
+                self.descend(Register::Left)
+            }
+
+            /// Returns the index of the rightmost node (highest key) of the tree.
+            fn rightmost(&self) -> Option<u8> {
+                self.descend(Register::Right)
+            }
+
+            /// Follows the specified branch from the root until reaching a node
+            /// without a child on that branch.
+            fn descend(&self, branch: Register) -> Option<u8> {
+                let mut node = self.allocator.get_field(Field::Root);
+
+                if node == SENTINEL {
+                    return None;
+                }
+
+                while node!(self.nodes, node).get_register(branch) != SENTINEL {
+                    node = node!(self.nodes, node).get_register(branch);
+                }
+
+                Some(node)
+            }
+
+            /// Returns the size of the subtree rooted at the specified node.
+            #[inline(always)]
+            fn size(&self, index: u8) -> u8 {
+                if index == SENTINEL {
+                    0
+                } else {
+                    node!(self.nodes, index).get_register(Register::Size)
+                }
+            }
+
+            /// Indicates whether the nodes store the sizes of their subtrees.
+            ///
+            /// The size of the root node matches the number of nodes in use, unless
+            /// the tree was written before sizes were stored.
+            fn has_sizes(&self) -> bool {
+                self.size(self.allocator.get_field(Field::Root)) as usize == self.len()
+            }
+
             fn find(&self, key: &K) -> Option<u8> {
+                self.find_borrowed(key)
+            }
+
+            /// Returns the index of the node with a key equal to the borrowed form of the
+            /// key, if any.
+            fn find_borrowed<Q: PartialOrd + ?Sized>(&self, key: &Q) -> Option<u8>
+            where
+                K: Borrow<Q>,
+            {
                 let mut reference_node = self.allocator.get_field(Field::Root);
 
                 while reference_node != SENTINEL {
-                    let current = node!(self.nodes, reference_node).key;
+                    let current = node!(self.nodes, reference_node).key.borrow();
 
-                    let target = if *key < current {
+                    let target = if key < current {
                         node!(self.nodes, reference_node).get_register(Register::Left)
-                    } else if *key > current {
+                    } else if key > current {
                         node!(self.nodes, reference_node).get_register(Register::Right)
                     } else {
                         return Some(reference_node);
@@ -121,6 +579,36 @@ macro_rules! readonly_impl {
                 None
             }
         }
+
+        impl<
+                'a,
+                K: Debug + PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+                V: Debug + Default + Copy + Clone + Pod + Zeroable,
+            > Debug for $name<'a, K, V>
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_map().entries(self.iter()).finish()
+            }
+        }
+
+        impl<
+                'a,
+                K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+                V: Default + Copy + Clone + Pod + Zeroable,
+            > Index<&K> for $name<'a, K, V>
+        {
+            type Output = V;
+
+            /// Returns a reference to the value under the specified key.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the key is not present in the tree.
+            fn index(&self, key: &K) -> &V {
+                let node_index = self.find(key).expect("key not found in the tree");
+                &node!(self.nodes, node_index).value
+            }
+        }
     };
 }
 
@@ -129,6 +617,7 @@ macro_rules! readonly_impl {
 /// most.
 ///
 /// This type can be used to reference a read-only tree.
+#[derive(Clone, Copy)]
 pub struct U8AVLTree<
     'a,
     K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
@@ -151,13 +640,67 @@ impl<
 {
     /// Loads a tree from a byte array.
     pub fn from_bytes(bytes: &'a [u8]) -> Self {
-        let (allocator, nodes) = bytes.split_at(std::mem::size_of::<U8Allocator>());
+        let (allocator, nodes) = bytes.split_at(core::mem::size_of::<U8Allocator>());
 
         let allocator = bytemuck::from_bytes::<U8Allocator>(allocator);
         let nodes = bytemuck::cast_slice(nodes);
 
         Self { allocator, nodes }
     }
+
+    /// Loads a tree from a byte array, validating the allocator fields.
+    ///
+    /// This should be used when the bytes are not trusted, since invalid allocator
+    /// fields could lead to out-of-bounds node access. The bytes must hold at least
+    /// `capacity` nodes; any additional nodes (e.g., when the bytes have been resized)
+    /// are ignored.
+    pub fn from_bytes_validated(bytes: &'a [u8]) -> Result<Self, TranslationError> {
+        if bytes.len() < core::mem::size_of::<U8Allocator>() {
+            return Err(TranslationError::InvalidLength {
+                expected: core::mem::size_of::<U8Allocator>(),
+                actual: bytes.len(),
+            });
+        }
+
+        let (allocator, nodes) = bytes.split_at(core::mem::size_of::<U8Allocator>());
+
+        let allocator = cast::try_from_bytes::<U8Allocator>(allocator)?;
+        let nodes: &[U8Node<K, V>] = cast::try_cast_slice(nodes)?;
+
+        let capacity = allocator.get_field(Field::Capacity);
+
+        if capacity as usize > nodes.len() {
+            return Err(TranslationError::InvalidLength {
+                expected: Self::data_len(capacity as usize),
+                actual: bytes.len(),
+            });
+        }
+
+        // the root can be the sentinel value (empty tree), while the free list head
+        // and sequence are one past the capacity when the tree is full
+        let limit = capacity as usize + 1;
+
+        if allocator.get_field(Field::Root) > capacity
+            || allocator.get_field(Field::Size) > capacity
+            || allocator.get_field(Field::FreeListHead) as usize > limit
+            || allocator.get_field(Field::Sequence) as usize > limit
+        {
+            return Err(TranslationError::InvalidValue);
+        }
+
+        Ok(Self { allocator, nodes })
+    }
+}
+
+impl<
+        'a,
+        K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+        V: Default + Copy + Clone + Pod + Zeroable,
+    > FromBytes<'a> for U8AVLTree<'a, K, V>
+{
+    fn from_bytes(bytes: &'a [u8]) -> Self {
+        U8AVLTree::from_bytes(bytes)
+    }
 }
 
 /// AVL tree struct, which is a self-balancing binary search tree. Values in the
@@ -187,7 +730,7 @@ impl<
 {
     /// Loads a tree from a byte array.
     pub fn from_bytes_mut(bytes: &'a mut [u8]) -> Self {
-        let (allocator, nodes) = bytes.split_at_mut(std::mem::size_of::<U8Allocator>());
+        let (allocator, nodes) = bytes.split_at_mut(core::mem::size_of::<U8Allocator>());
 
         let allocator = bytemuck::from_bytes_mut::<U8Allocator>(allocator);
         let nodes: &mut [U8Node<K, V>] = bytemuck::cast_slice_mut(nodes);
@@ -228,10 +771,43 @@ impl<
     /// Initializes the tree with the specified capacity.
     ///
     /// This function should be called once when the tree is created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is greater than the number of nodes that fit in the
+    /// backing bytes or than the maximum capacity of a tree.
     pub fn initialize(&mut self, capacity: u8) {
+        assert!(
+            capacity as usize <= self.nodes.len(),
+            "capacity ({}) exceeds the number of nodes in the data ({})",
+            capacity,
+            self.nodes.len()
+        );
+        assert!(
+            capacity as usize <= MAX_CAPACITY,
+            "capacity ({}) exceeds the maximum capacity ({})",
+            capacity,
+            MAX_CAPACITY
+        );
+
         self.allocator.initialize(capacity)
     }
 
+    /// Resets the tree to an empty state, keeping its capacity.
+    ///
+    /// The allocator is zeroed and re-initialized with the current capacity, and the
+    /// node storage is zeroed, so the tree is in the same state as a newly initialized
+    /// one. The tree can be used right away; there is no need to reload it with
+    /// [`Self::from_bytes_mut`].
+    pub fn reset(&mut self) {
+        let capacity = self.allocator.get_field(Field::Capacity);
+
+        bytemuck::fill_zeroes(core::slice::from_mut(self.allocator));
+        self.allocator.initialize(capacity);
+
+        bytemuck::fill_zeroes(&mut self.nodes[..capacity as usize]);
+    }
+
     /// Return a mutable reference to the  value under the specified key, if one is found.
     ///
     /// # Arguments
@@ -242,6 +818,34 @@ impl<
             .map(|node_index| &mut node!(self.nodes, node_index).value)
     }
 
+    /// Returns mutable references to the values under two different keys.
+    ///
+    /// Returns `None` if the keys are the same or either of them is not found.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - key to look up the first value.
+    /// * `b` - key to look up the second value.
+    pub fn get_disjoint_mut(&mut self, a: &K, b: &K) -> Option<(&mut V, &mut V)> {
+        let a = self.find(a)? as usize - 1;
+        let b = self.find(b)? as usize - 1;
+
+        match a.cmp(&b) {
+            Ordering::Less => {
+                // the node of a is on the left side of the split
+                let (left, right) = self.nodes.split_at_mut(b);
+                Some((&mut left[a].value, &mut right[0].value))
+            }
+            Ordering::Greater => {
+                // the node of b is on the left side of the split
+                let (left, right) = self.nodes.split_at_mut(a);
+                Some((&mut right[0].value, &mut left[b].value))
+            }
+            // both keys refer to the same node
+            Ordering::Equal => None,
+        }
+    }
+
     /// Insert a value on the tree at the specified key.
     ///
     /// The value is inserted in the tree maintaining the natural order based on
@@ -250,7 +854,7 @@ impl<
     /// # Arguments
     ///
     /// * `key` - the key of the node.
-    /// • `value` - the value of the node.
+    /// * `value` - the value of the node.
     pub fn insert(&mut self, key: K, value: V) -> Option<u8> {
         let mut reference_node = self.allocator.get_field(Field::Root);
 
@@ -260,7 +864,7 @@ impl<
             return Some(root);
         }
 
-        let mut path: Vec<Ancestor> = Vec::with_capacity((self.len() as f64).log2() as usize);
+        let mut path = Path::new();
         path.push((None, None, reference_node));
 
         loop {
@@ -304,7 +908,7 @@ impl<
             return None;
         }
 
-        let mut path: Vec<Ancestor> = Vec::with_capacity((self.len() as f64).log2() as usize);
+        let mut path = Path::new();
         path.push((None, None, node_index));
 
         while node_index != SENTINEL {
@@ -336,7 +940,7 @@ impl<
             let mut leftmost = right;
             let mut leftmost_parent = SENTINEL;
             // path to the leftmost descendant
-            let mut inner_path = Vec::with_capacity((self.len() as f64).log2() as usize);
+            let mut inner_path = Path::new();
 
             while node!(self.nodes, leftmost).get_register(Register::Left) != SENTINEL {
                 leftmost_parent = leftmost;
@@ -369,10 +973,11 @@ impl<
                 path.push((Some(leftmost), Some(Register::Right), right));
             }
             // drop the last inner_path element since it references the leftmost node
-            if !inner_path.is_empty() {
-                inner_path.pop();
-            }
-            path.extend(inner_path);
+            inner_path.pop();
+            inner_path
+                .as_slice()
+                .iter()
+                .for_each(|ancestor| path.push(*ancestor));
 
             leftmost
         } else {
@@ -406,42 +1011,165 @@ impl<
         self.remove_node(node_index)
     }
 
-    /// Adds a node to the tree.
+    /// Returns an iterator over mutable references to the values of the tree, sorted
+    /// by key.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(self.nodes, self.allocator.get_field(Field::Root))
+    }
+
+    /// Retains only the entries specified by the predicate.
     ///
-    /// The node is only added if there is space on the tree.
+    /// The predicate is called once for each entry in ascending key order and can
+    /// modify the value; entries for which it returns `false` are removed from the
+    /// tree, which is rebalanced as needed.
     ///
-    /// # Arguments
+    /// Entries are visited by searching for the successor of the previous key, so
+    /// this does not require any allocation and runs in O(n log n).
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        let mut next = self.leftmost();
+
+        while let Some(node_index) = next {
+            let node = &mut node!(self.nodes, node_index);
+            let key = node.key;
+            let keep = f(&key, &mut node.value);
+            // the successor is found by key, so it is not affected by the removal
+            // of the current node
+            next = self.successor(&key);
+
+            if !keep {
+                self.remove(&key);
+            }
+        }
+    }
+
+    /// Rebuilds the tree so that nodes are stored contiguously in key order.
     ///
-    /// * `key` - the key of the node.
-    /// * `value` - the value of the node.
-    fn add(&mut self, key: K, value: V) -> u8 {
-        let free_node = self.allocator.get_field(Field::FreeListHead);
-        let sequence = self.allocator.get_field(Field::Sequence);
+    /// After many insertions and removals, nodes in use are interleaved with free
+    /// nodes. This moves the nodes in use to the start of the storage sorted by key,
+    /// links them as a balanced tree and resets the free list, so the free nodes are
+    /// contiguous at the end of the storage.
+    ///
+    /// The nodes are rearranged in place, so this does not require any allocation
+    /// and runs in O(n log n).
+    pub fn compact(&mut self) {
+        let capacity = self.capacity();
+        let size = self.len();
+        // marks the nodes in use using the size register, which is recalculated
+        // when the nodes are linked
+        self.nodes[..capacity]
+            .iter_mut()
+            .for_each(|node| node.set_register(Register::Size, 0));
+
+        let mut order = InOrder::new(self.nodes, self.allocator.get_field(Field::Root), false);
+
+        while let Some(index) = order.next(self.nodes) {
+            node!(self.nodes, index).set_register(Register::Size, 1);
+        }
+        // moves the nodes in use to the start of the storage
+        let mut position = 0;
 
-        if free_node == sequence {
-            if (sequence - 1) == self.allocator.get_field(Field::Capacity) {
-                panic!(
-                    "tree is full ({} nodes)",
-                    self.allocator.get_field(Field::Size)
-                );
+        for index in 0..capacity {
+            if self.nodes[index].get_register(Register::Size) != 0 {
+                self.nodes.swap(position, index);
+                position += 1;
             }
-
-            self.allocator.set_field(Field::Sequence, sequence + 1);
-            self.allocator.set_field(Field::FreeListHead, sequence + 1);
-        } else {
-            self.allocator.set_field(
-                Field::FreeListHead,
-                node!(self.nodes, free_node).get_register(Register::Height),
-            );
         }
 
-        let entry = &mut node!(self.nodes, free_node);
+        self.nodes[..size].sort_unstable_by(|a, b| {
+            a.key
+                .partial_cmp(&b.key)
+                .expect("keys must have a total order")
+        });
+        // clears the free nodes
+        self.nodes[size..capacity]
+            .iter_mut()
+            .for_each(|node| node.initialize(K::default(), V::default()));
+
+        let root = self.link(0, size);
+
+        self.allocator.set_field(Field::Root, root);
+        // free nodes follow the nodes in use
+        self.allocator
+            .set_field(Field::FreeListHead, size as u8 + 1);
+        self.allocator.set_field(Field::Sequence, size as u8 + 1);
+    }
+
+    /// Links the sorted nodes in the range `[start, end)` as a balanced subtree,
+    /// returning the index of its root.
+    fn link(&mut self, start: usize, end: usize) -> u8 {
+        if start == end {
+            return SENTINEL;
+        }
+
+        let middle = start + (end - start) / 2;
+        let left = self.link(start, middle);
+        let right = self.link(middle + 1, end);
+        // nodes are indexed starting from 1
+        let index = middle as u8 + 1;
+        let node = &mut node!(self.nodes, index);
+
+        node.registers = [left, right, 0, 0];
+        self.update_height(index);
+
+        index
+    }
+
+    /// Returns the index of the node with the lowest key greater than the specified key.
+    fn successor(&self, key: &K) -> Option<u8> {
+        let mut node_index = self.allocator.get_field(Field::Root);
+        let mut successor = None;
+
+        while node_index != SENTINEL {
+            let node = &node!(self.nodes, node_index);
+
+            node_index = if *key < node.key {
+                successor = Some(node_index);
+                node.get_register(Register::Left)
+            } else {
+                node.get_register(Register::Right)
+            };
+        }
+
+        successor
+    }
+
+    /// Adds a node to the tree.
+    ///
+    /// The node is only added if there is space on the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key of the node.
+    /// * `value` - the value of the node.
+    fn add(&mut self, key: K, value: V) -> u8 {
+        let free_node = self.allocator.get_field(Field::FreeListHead);
+        let sequence = self.allocator.get_field(Field::Sequence);
+
+        if free_node == sequence {
+            if (sequence - 1) == self.allocator.get_field(Field::Capacity) {
+                panic!(
+                    "tree is full ({} nodes)",
+                    self.allocator.get_field(Field::Size)
+                );
+            }
+
+            self.allocator.set_field(Field::Sequence, sequence + 1);
+            self.allocator.set_field(Field::FreeListHead, sequence + 1);
+        } else {
+            self.allocator.set_field(
+                Field::FreeListHead,
+                node!(self.nodes, free_node).get_register(Register::Height),
+            );
+        }
+
+        let entry = &mut node!(self.nodes, free_node);
 
         entry.key = key;
         entry.value = value;
         // the height field is used to store the free list head, so we make
         // sure we reset its value
         entry.set_register(Register::Height, 0);
+        entry.set_register(Register::Size, 1);
 
         self.allocator
             .set_field(Field::Size, self.allocator.get_field(Field::Size) + 1);
@@ -457,8 +1185,8 @@ impl<
     /// # Arguments
     ///
     /// * `path` - path to rebalance. The path is visited in reverse order.
-    fn rebalance(&mut self, path: Vec<Ancestor>) {
-        for (parent, branch, child) in path.iter().rev() {
+    fn rebalance(&mut self, path: Path) {
+        for (parent, branch, child) in path.as_slice().iter().rev() {
             let left = node!(self.nodes, *child).get_register(Register::Left);
             let right = node!(self.nodes, *child).get_register(Register::Right);
 
@@ -577,9 +1305,10 @@ impl<
         self.update_height(parent);
     }
 
-    /// Updates the height of a node.
+    /// Updates the height and subtree size of a node.
     ///
-    /// The height of a node is determined by the height of the larger child's subtree plus one.
+    /// The height of a node is determined by the height of the larger child's subtree plus one,
+    /// while the size is the sum of the sizes of the children's subtrees plus one.
     ///
     /// # Arguments
     ///
@@ -605,7 +1334,10 @@ impl<
             max(left_height, right_height) + 1
         };
 
+        let size = 1 + self.size(left) + self.size(right);
+
         node!(self.nodes, index).set_register(Register::Height, height);
+        node!(self.nodes, index).set_register(Register::Size, size);
     }
 
     /// Remove a node from the tree, returning its value.
@@ -632,6 +1364,22 @@ impl<
     }
 }
 
+impl<
+        'a,
+        K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+        V: Default + Copy + Clone + Pod + Zeroable,
+    > IndexMut<&K> for U8AVLTreeMut<'a, K, V>
+{
+    /// Returns a mutable reference to the value under the specified key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the tree.
+    fn index_mut(&mut self, key: &K) -> &mut V {
+        self.get_mut(key).expect("key not found in the tree")
+    }
+}
+
 /// The allocator is responsible to keep track of the status of the tree.
 ///
 /// It uses two special fields to determine if the tree is full and to reuse
@@ -669,7 +1417,7 @@ impl U8Allocator {
 }
 
 impl Debug for U8Allocator {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Allocator")
             .field("root", &self.fields[0])
             .field("size", &self.fields[1])
@@ -680,6 +1428,237 @@ impl Debug for U8Allocator {
     }
 }
 
+/// Stack-allocated state of an in-order traversal of a tree.
+///
+/// The traversal keeps the nodes still to be visited, which is bounded by the height
+/// of the tree. The nodes are passed on each step, so the tree can be modified between
+/// steps as long as the links of the nodes are not.
+///
+/// The traversal can be in ascending or descending key order.
+struct InOrder {
+    /// Nodes to be visited, where the last one is the next node.
+    stack: [u8; MAX_PATH_LENGTH],
+
+    /// Number of nodes in the stack.
+    length: usize,
+
+    /// Branch followed first from each node: `Left` for ascending order and
+    /// `Right` for descending order.
+    first: Register,
+
+    /// Branch followed after visiting a node.
+    second: Register,
+}
+
+impl InOrder {
+    fn new<
+        K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+        V: Default + Copy + Clone + Pod + Zeroable,
+    >(
+        nodes: &[U8Node<K, V>],
+        root: u8,
+        reverse: bool,
+    ) -> Self {
+        let (first, second) = if reverse {
+            (Register::Right, Register::Left)
+        } else {
+            (Register::Left, Register::Right)
+        };
+        let mut order = Self {
+            stack: [SENTINEL; MAX_PATH_LENGTH],
+            length: 0,
+            first,
+            second,
+        };
+        order.push_first(nodes, root);
+        order
+    }
+
+    /// Returns the index of the next node, if any.
+    fn next<
+        K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+        V: Default + Copy + Clone + Pod + Zeroable,
+    >(
+        &mut self,
+        nodes: &[U8Node<K, V>],
+    ) -> Option<u8> {
+        if self.length == 0 {
+            return None;
+        }
+
+        self.length -= 1;
+        let index = self.stack[self.length];
+        self.push_first(nodes, node!(nodes, index).get_register(self.second));
+
+        Some(index)
+    }
+
+    /// Pushes the node and all its descendants on the first branch to the stack.
+    fn push_first<
+        K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+        V: Default + Copy + Clone + Pod + Zeroable,
+    >(
+        &mut self,
+        nodes: &[U8Node<K, V>],
+        mut node: u8,
+    ) {
+        while node != SENTINEL {
+            self.stack[self.length] = node;
+            self.length += 1;
+            node = node!(nodes, node).get_register(self.first);
+        }
+    }
+}
+
+/// In-order iterator over the entries of a tree, in ascending or descending key order.
+pub struct Iter<
+    'a,
+    K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+    V: Default + Copy + Clone + Pod + Zeroable,
+> {
+    /// Array of nodes of the tree.
+    nodes: &'a [U8Node<K, V>],
+
+    /// State of the traversal.
+    order: InOrder,
+}
+
+impl<
+        'a,
+        K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+        V: Default + Copy + Clone + Pod + Zeroable,
+    > Iter<'a, K, V>
+{
+    fn new(nodes: &'a [U8Node<K, V>], root: u8, reverse: bool) -> Self {
+        Self {
+            nodes,
+            order: InOrder::new(nodes, root, reverse),
+        }
+    }
+}
+
+impl<
+        'a,
+        K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+        V: Default + Copy + Clone + Pod + Zeroable,
+    > Iterator for Iter<'a, K, V>
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = &node!(self.nodes, self.order.next(self.nodes)?);
+        Some((&node.key, &node.value))
+    }
+}
+
+/// In-order iterator over mutable references to the values of a tree.
+///
+/// Keys are not exposed, since modifying them would break the order of the tree.
+pub struct ValuesMut<
+    'a,
+    K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+    V: Default + Copy + Clone + Pod + Zeroable,
+> {
+    /// Pointer to the array of nodes of the tree.
+    ///
+    /// A raw pointer is used since the iterator reads the registers of nodes
+    /// while holding mutable references to the values of visited nodes.
+    nodes: *mut U8Node<K, V>,
+
+    /// Number of nodes in the array.
+    capacity: usize,
+
+    /// Nodes to be visited, where the last one is the next node.
+    stack: [u8; MAX_PATH_LENGTH],
+
+    /// Number of nodes in the stack.
+    length: usize,
+
+    /// Bitmap of nodes pushed to the stack, indexed by node index.
+    ///
+    /// Each node is pushed at most once, which guarantees that a mutable
+    /// reference is handed out at most once per node, even if the registers
+    /// of a malformed tree reference a node more than once.
+    pushed: [u64; 4],
+
+    _marker: PhantomData<&'a mut [U8Node<K, V>]>,
+}
+
+impl<
+        'a,
+        K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+        V: Default + Copy + Clone + Pod + Zeroable,
+    > ValuesMut<'a, K, V>
+{
+    fn new(nodes: &'a mut [U8Node<K, V>], root: u8) -> Self {
+        let mut iter = Self {
+            capacity: nodes.len(),
+            nodes: nodes.as_mut_ptr(),
+            stack: [SENTINEL; MAX_PATH_LENGTH],
+            length: 0,
+            pushed: [0; 4],
+            _marker: PhantomData,
+        };
+        iter.push_left(root);
+        iter
+    }
+
+    /// Returns a pointer to the node at the specified index.
+    fn node(&self, index: u8) -> *mut U8Node<K, V> {
+        assert!(index != SENTINEL && index as usize <= self.capacity);
+        // safety: nodes are indexed starting from 1 and the index is within
+        // the bounds of the array
+        unsafe { self.nodes.add(index as usize - 1) }
+    }
+
+    /// Pushes the node and all its left descendants to the stack.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node has already been pushed, which can only happen if the
+    /// tree structure is invalid.
+    fn push_left(&mut self, mut node: u8) {
+        while node != SENTINEL {
+            let (word, bit) = (node as usize / 64, 1u64 << (node % 64));
+            assert!(self.pushed[word] & bit == 0, "invalid tree structure");
+            self.pushed[word] |= bit;
+
+            self.stack[self.length] = node;
+            self.length += 1;
+            // safety: the node has just been pushed for the first time, so no
+            // reference to its value has been handed out
+            node = unsafe { (*self.node(node)).get_register(Register::Left) };
+        }
+    }
+}
+
+impl<
+        'a,
+        K: PartialOrd + Default + Copy + Clone + Pod + Zeroable,
+        V: Default + Copy + Clone + Pod + Zeroable,
+    > Iterator for ValuesMut<'a, K, V>
+{
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.length == 0 {
+            return None;
+        }
+
+        self.length -= 1;
+        let node = self.node(self.stack[self.length]);
+        // safety: nodes are popped from the stack once, so no reference to the
+        // value of this node has been handed out yet
+        let right = unsafe { (*node).get_register(Register::Right) };
+        self.push_left(right);
+
+        // safety: `push_left` panics when a node would be pushed a second time,
+        // so each node is popped at most once and the mutable references to the
+        // values do not alias
+        Some(unsafe { &mut (*node).value })
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Default)]
 pub struct U8Node<
@@ -690,7 +1669,7 @@ pub struct U8Node<
     ///   [0] - left child
     ///   [1] - right child
     ///   [2] - height
-    ///   [3] - not in use (padding)
+    ///   [3] - size of the subtree rooted at the node
     registers: [u8; 4],
     /// Account key.
     key: K,
@@ -735,7 +1714,15 @@ unsafe impl<
 
 #[cfg(test)]
 mod tests {
-    use crate::collections::{u8_avl_tree::U8Node, U8AVLTree, U8AVLTreeMut};
+    use std::{format, vec, vec::Vec};
+
+    use crate::{
+        collections::{
+            u8_avl_tree::{Field, Register, U8Node, MAX_CAPACITY, SENTINEL},
+            U8AVLTree, U8AVLTreeMut,
+        },
+        TranslationError,
+    };
 
     #[test]
     fn test_insert() {
@@ -1015,4 +2002,812 @@ mod tests {
         assert_eq!(readonly_tree.len(), CAPACITY);
         assert_eq!(readonly_tree.capacity(), CAPACITY);
     }
+
+    #[test]
+    fn test_from_bytes_validated() {
+        const CAPACITY: usize = 10;
+
+        let mut data = vec![0u8; U8AVLTreeMut::<u32, u32>::data_len(CAPACITY)];
+        let mut tree = U8AVLTreeMut::from_bytes_mut(&mut data);
+
+        tree.allocator.initialize(CAPACITY as u8);
+
+        for i in 0..CAPACITY {
+            tree.insert(i as u32, i as u32);
+        }
+
+        let tree = U8AVLTree::<u32, u32>::from_bytes_validated(&data).unwrap();
+        assert_eq!(tree.len(), CAPACITY);
+        assert_eq!(tree.get(&5), Some(5));
+
+        // allocator fields: [root, size, capacity, free_list_head, sequence]
+        for (field, value) in [(0, 11), (1, 11), (3, 12), (4, 12)] {
+            let mut tampered = data.clone();
+            tampered[field] = value;
+
+            assert_eq!(
+                U8AVLTree::<u32, u32>::from_bytes_validated(&tampered).err(),
+                Some(TranslationError::InvalidValue)
+            );
+        }
+
+        // capacity larger than the available nodes
+        let mut tampered = data.clone();
+        tampered[2] = CAPACITY as u8 + 1;
+
+        assert_eq!(
+            U8AVLTree::<u32, u32>::from_bytes_validated(&tampered).err(),
+            Some(TranslationError::InvalidLength {
+                expected: U8AVLTree::<u32, u32>::data_len(CAPACITY + 1),
+                actual: data.len()
+            })
+        );
+
+        // bytes not holding the allocator
+        assert_eq!(
+            U8AVLTree::<u32, u32>::from_bytes_validated(&data[..4]).err(),
+            Some(TranslationError::InvalidLength {
+                expected: 8,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_insert_remove_unordered() {
+        const CAPACITY: usize = 254;
+
+        let mut data = [0u8; U8AVLTreeMut::<u32, u32>::data_len(CAPACITY)];
+        let mut tree = U8AVLTreeMut::from_bytes_mut(&mut data);
+        tree.allocator.initialize(CAPACITY as u8);
+
+        // 97 is coprime with 254, so this visits every key in a scattered order
+        let key = |i: usize| ((i * 97) % CAPACITY) as u32;
+
+        for i in 0..CAPACITY {
+            assert!(tree.insert(key(i), key(i) * 2).is_some());
+        }
+
+        assert_eq!(tree.len(), CAPACITY);
+
+        for i in (0..CAPACITY).step_by(2) {
+            assert_eq!(tree.remove(&key(i)), Some(key(i) * 2));
+        }
+
+        assert_eq!(tree.len(), CAPACITY / 2);
+
+        for i in 0..CAPACITY {
+            let expected = if i % 2 == 0 { None } else { Some(key(i) * 2) };
+            assert_eq!(tree.get(&key(i)), expected);
+        }
+    }
+
+    #[test]
+    fn test_first_last_key_value() {
+        const CAPACITY: usize = 10;
+
+        let mut data = [0u8; U8AVLTreeMut::<u32, u32>::data_len(CAPACITY)];
+        let mut tree = U8AVLTreeMut::from_bytes_mut(&mut data);
+        tree.allocator.initialize(CAPACITY as u8);
+
+        assert!(tree.first_key_value().is_none());
+        assert!(tree.last_key_value().is_none());
+
+        for key in [5, 3, 8, 1, 9, 4] {
+            tree.insert(key, key * 10);
+        }
+
+        assert_eq!(tree.first_key_value(), Some((&1, &10)));
+        assert_eq!(tree.last_key_value(), Some((&9, &90)));
+
+        tree.remove(&1);
+        tree.remove(&9);
+
+        assert_eq!(tree.first_key_value(), Some((&3, &30)));
+        assert_eq!(tree.last_key_value(), Some((&8, &80)));
+    }
+
+    #[test]
+    fn test_max_capacity() {
+        assert_eq!(U8AVLTree::<u8, u8>::max_capacity(), 254);
+        assert_eq!(U8AVLTreeMut::<u8, u8>::max_capacity(), 254);
+    }
+
+    #[test]
+    fn test_min_max_key() {
+        const CAPACITY: usize = 100;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        assert_eq!(tree.min_key(), None);
+        assert_eq!(tree.max_key(), None);
+
+        let mut reference = std::collections::BTreeSet::new();
+
+        for i in 0..CAPACITY as u32 {
+            // inserts the keys in a scrambled order
+            let key = (i * 37) % 101;
+            tree.insert(key, i);
+            reference.insert(key);
+
+            assert_eq!(tree.min_key(), reference.first().copied());
+            assert_eq!(tree.max_key(), reference.last().copied());
+        }
+
+        for i in 0..CAPACITY as u32 {
+            let key = (i * 53) % 101;
+
+            if reference.remove(&key) {
+                tree.remove(&key);
+            }
+
+            assert_eq!(tree.min_key(), reference.first().copied());
+            assert_eq!(tree.max_key(), reference.last().copied());
+        }
+    }
+
+    #[test]
+    fn test_iter_and_debug() {
+        const CAPACITY: usize = 20;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        assert_eq!(format!("{:?}", tree), "{}");
+
+        for key in [5, 1, 4, 2, 3] {
+            tree.insert(key, key * 10);
+        }
+
+        let tree = U8AVLTree::<u32, u32>::from_bytes(&data);
+        let entries: Vec<(u32, u32)> = tree.iter().map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(entries, [(1, 10), (2, 20), (3, 30), (4, 40), (5, 50)]);
+
+        assert_eq!(format!("{:?}", tree), "{1: 10, 2: 20, 3: 30, 4: 40, 5: 50}");
+    }
+
+    #[test]
+    fn test_get_disjoint_mut() {
+        const CAPACITY: usize = 10;
+        let mut data = vec![0u8; U8AVLTree::<u32, u64>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u64>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        tree.insert(1, 100);
+        tree.insert(2, 50);
+        tree.insert(3, 0);
+
+        // transfers between two entries
+        let (from, to) = tree.get_disjoint_mut(&1, &3).unwrap();
+        *from -= 30;
+        *to += 30;
+
+        let (from, to) = tree.get_disjoint_mut(&3, &2).unwrap();
+        *from -= 10;
+        *to += 10;
+
+        assert_eq!(tree.get(&1), Some(70));
+        assert_eq!(tree.get(&2), Some(60));
+        assert_eq!(tree.get(&3), Some(20));
+
+        // the same key cannot be borrowed twice
+        assert!(tree.get_disjoint_mut(&1, &1).is_none());
+        // missing keys
+        assert!(tree.get_disjoint_mut(&1, &4).is_none());
+        assert!(tree.get_disjoint_mut(&4, &1).is_none());
+    }
+
+    #[test]
+    fn test_retain() {
+        const CAPACITY: usize = 254;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        for i in 0..CAPACITY as u32 {
+            // inserts the keys in a scrambled order
+            let key = (i * 37) % 254;
+            tree.insert(key, key);
+        }
+
+        // removes the even keys and updates the others
+        tree.retain(|key, value| {
+            *value *= 10;
+            key % 2 == 1
+        });
+
+        assert_eq!(tree.len(), CAPACITY / 2);
+
+        let entries: Vec<(u32, u32)> = tree.iter().map(|(k, v)| (*k, *v)).collect();
+        let expected: Vec<(u32, u32)> = (0..CAPACITY as u32)
+            .filter(|key| key % 2 == 1)
+            .map(|key| (key, key * 10))
+            .collect();
+        assert_eq!(entries, expected);
+
+        // removed nodes can be reused
+        for key in (0..CAPACITY as u32).filter(|key| key % 2 == 0) {
+            assert!(tree.insert(key, key).is_some());
+        }
+        assert_eq!(tree.len(), CAPACITY);
+
+        // removes all entries
+        tree.retain(|_, _| false);
+        assert!(tree.is_empty());
+        assert_eq!(tree.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_values_mut() {
+        const CAPACITY: usize = 100;
+        let mut data = vec![0u8; U8AVLTree::<u32, u64>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u64>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        for i in 0..CAPACITY as u32 {
+            let key = (i * 37) % 100;
+            tree.insert(key, key as u64);
+        }
+        // removed nodes are not visited
+        tree.remove(&50);
+
+        let mut count = 0;
+        for value in tree.values_mut() {
+            *value *= 2;
+            count += 1;
+        }
+        assert_eq!(count, CAPACITY - 1);
+
+        for key in 0..CAPACITY as u32 {
+            if key == 50 {
+                assert_eq!(tree.get(&key), None);
+            } else {
+                assert_eq!(tree.get(&key), Some(key as u64 * 2));
+            }
+        }
+
+        // values are visited in key order
+        let values: Vec<u64> = tree.values_mut().map(|value| *value).collect();
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid tree structure")]
+    fn test_values_mut_invalid_tree() {
+        const CAPACITY: usize = 3;
+        let mut data = vec![0u8; U8AVLTree::<u32, u64>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u64>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        tree.insert(2, 2);
+        tree.insert(1, 1);
+        tree.insert(3, 3);
+        // both children of the root reference the same node
+        let left = tree.nodes[0].get_register(Register::Left);
+        tree.nodes[0].set_register(Register::Right, left);
+
+        let _ = tree.values_mut().collect::<Vec<_>>();
+    }
+
+    #[test]
+    fn test_get_many() {
+        const CAPACITY: usize = 50;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        for key in (0..CAPACITY as u32).map(|i| i * 2) {
+            tree.insert(key, key + 1);
+        }
+
+        let tree = U8AVLTree::<u32, u32>::from_bytes(&data);
+        let keys = [7, 0, 98, 3, 42, 100, 42];
+
+        let values: Vec<Option<u32>> = tree.get_many(&keys).collect();
+        let expected: Vec<Option<u32>> = keys.iter().map(|key| tree.get(key)).collect();
+
+        assert_eq!(values, expected);
+        assert_eq!(
+            values,
+            [None, Some(1), Some(99), None, Some(43), None, Some(43)]
+        );
+    }
+
+    #[test]
+    fn test_get_or_default() {
+        const CAPACITY: usize = 10;
+        let mut data = vec![0u8; U8AVLTree::<u32, u64>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u64>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+        tree.insert(1, 10);
+        tree.insert(2, 0);
+
+        let tree = U8AVLTree::<u32, u64>::from_bytes(&data);
+
+        assert_eq!(tree.get_or_default(&1), 10);
+        assert_eq!(tree.get_or_default(&2), 0);
+        assert_eq!(tree.get_or_default(&3), 0);
+    }
+
+    #[test]
+    fn test_min_max_by_value() {
+        const CAPACITY: usize = 10;
+        let mut data = vec![0u8; U8AVLTree::<u32, u64>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u64>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+        assert_eq!(tree.max_by_value(|a, b| a.cmp(b)), None);
+
+        for (key, value) in [(5, 20), (1, 70), (8, 5), (3, 70), (6, 40)] {
+            tree.insert(key, value);
+        }
+
+        let tree = U8AVLTree::<u32, u64>::from_bytes(&data);
+        // ties are resolved by the key order
+        assert_eq!(tree.max_by_value(|a, b| a.cmp(b)), Some((&3, &70)));
+        assert_eq!(tree.min_by_value(|a, b| a.cmp(b)), Some((&8, &5)));
+        assert_eq!(tree.max_by_value(|a, b| b.cmp(a)), Some((&8, &5)));
+    }
+
+    #[test]
+    fn test_used_len() {
+        const CAPACITY: usize = 10;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY + 2)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(
+            &mut data[..U8AVLTree::<u32, u32>::data_len(CAPACITY)],
+        );
+        tree.initialize(CAPACITY as u8);
+        tree.insert(1, 1);
+        tree.insert(2, 2);
+
+        // free nodes are included
+        let expected = U8AVLTree::<u32, u32>::data_len(CAPACITY);
+        assert_eq!(tree.used_len(), expected);
+
+        // trailing bytes are not
+        let tree = U8AVLTree::<u32, u32>::from_bytes(&data);
+        assert_eq!(tree.used_len(), expected);
+    }
+
+    #[test]
+    fn test_index() {
+        const CAPACITY: usize = 10;
+        let mut data = vec![0u8; U8AVLTree::<u32, u64>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u64>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+        tree.insert(1, 10);
+        tree.insert(2, 20);
+
+        tree[&2] += 5;
+        assert_eq!(tree[&1], 10);
+
+        let tree = U8AVLTree::<u32, u64>::from_bytes(&data);
+        assert_eq!(tree[&1], 10);
+        assert_eq!(tree[&2], 25);
+    }
+
+    #[test]
+    #[should_panic(expected = "key not found in the tree")]
+    fn test_index_missing_key() {
+        const CAPACITY: usize = 10;
+        let mut data = vec![0u8; U8AVLTree::<u32, u64>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u64>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+        tree.insert(1, 10);
+
+        let tree = U8AVLTree::<u32, u64>::from_bytes(&data);
+        let _ = tree[&2];
+    }
+
+    #[test]
+    fn test_height_is_balanced() {
+        const CAPACITY: usize = 254;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+        assert_eq!(tree.height(), 0);
+        assert!(tree.is_balanced());
+
+        tree.insert(0, 0);
+        assert_eq!(tree.height(), 1);
+
+        for key in 1..CAPACITY as u32 {
+            tree.insert(key, key);
+            assert!(tree.is_balanced());
+        }
+
+        // the height of an AVL tree is at most 1.44 * log2(n + 2)
+        let bound = (1.44 * ((CAPACITY + 2) as f64).log2()) as usize;
+        assert!(tree.height() <= bound);
+
+        for key in (0..CAPACITY as u32).step_by(2) {
+            tree.remove(&key);
+            assert!(tree.is_balanced());
+        }
+
+        // a cycle in the registers is not balanced
+        let root = tree.allocator.get_field(Field::Root);
+        tree.nodes[root as usize - 1].set_register(Register::Left, root);
+        assert!(!tree.is_balanced());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_to_owned_bytes() {
+        const CAPACITY: usize = 10;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY + 1)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(
+            &mut data[..U8AVLTree::<u32, u32>::data_len(CAPACITY)],
+        );
+        tree.initialize(CAPACITY as u8);
+        for key in [3, 1, 2] {
+            tree.insert(key, key * 10);
+        }
+
+        // the bytes after the capacity are not copied
+        let owned = U8AVLTree::<u32, u32>::from_bytes(&data).to_owned_bytes();
+        assert_eq!(owned.len(), U8AVLTree::<u32, u32>::data_len(CAPACITY));
+
+        let tree = U8AVLTree::<u32, u32>::from_bytes(&owned);
+        let entries: Vec<(u32, u32)> = tree.iter().map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(entries, [(1, 10), (2, 20), (3, 30)]);
+        assert_eq!(tree.capacity(), CAPACITY);
+    }
+
+    #[test]
+    fn test_get_borrowed() {
+        const CAPACITY: usize = 10;
+        let mut data = vec![0u8; U8AVLTree::<[u8; 4], u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<[u8; 4], u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+        tree.insert(*b"abcd", 1);
+        tree.insert(*b"bcde", 2);
+        tree.insert(*b"abce", 3);
+
+        let tree = U8AVLTree::<[u8; 4], u32>::from_bytes(&data);
+        let key: &[u8] = &b"xabce"[1..];
+
+        assert_eq!(tree.get_borrowed(key), Some(3));
+        assert_eq!(tree.get_borrowed(&b"bcde"[..]), Some(2));
+        assert!(tree.contains_borrowed(&b"abcd"[..]));
+        // slices with a different length do not match
+        assert_eq!(tree.get_borrowed(&b"abc"[..]), None);
+        assert!(!tree.contains_borrowed(&b"abcde"[..]));
+    }
+
+    #[test]
+    fn test_allocator_stats() {
+        const CAPACITY: usize = 10;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        assert_eq!(tree.free_count(), 10);
+        assert!(!tree.is_fragmented());
+
+        for key in 0..5 {
+            tree.insert(key, key);
+        }
+        assert_eq!(tree.free_count(), 5);
+        assert!(!tree.is_fragmented());
+
+        // removing a node leaves a free slot in the middle of the storage
+        tree.remove(&2);
+        assert_eq!(tree.free_count(), 6);
+        assert!(tree.is_fragmented());
+
+        // the free slot is reused
+        tree.insert(10, 10);
+        assert_eq!(tree.free_count(), 5);
+        assert!(!tree.is_fragmented());
+
+        for key in 11..16 {
+            tree.insert(key, key);
+        }
+        assert_eq!(tree.free_count(), 0);
+        assert!(!tree.is_fragmented());
+
+        tree.remove(&0);
+        tree.remove(&15);
+        assert_eq!(tree.free_count(), 2);
+        assert!(tree.is_fragmented());
+
+        // does not underflow when the size exceeds the capacity
+        tree.allocator.set_field(Field::Size, CAPACITY as u8 + 1);
+        assert_eq!(tree.free_count(), 0);
+    }
+
+    #[test]
+    fn test_remove_two_children() {
+        const CAPACITY: usize = MAX_CAPACITY;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        let mut keys: Vec<u32> = (0..CAPACITY as u32).map(|i| (i * 97) % 257).collect();
+        for key in &keys {
+            tree.insert(*key, key * 2);
+        }
+        keys.sort();
+
+        // removes the root repeatedly, which has two children while the tree has more
+        // than two nodes; this exercises both the case where the replacement is the
+        // right child and where it is a deeper leftmost descendant
+        while tree.len() > 2 {
+            let (root_key, left, right) = {
+                let root = &node!(tree.nodes, tree.allocator.get_field(Field::Root));
+                (
+                    root.key,
+                    root.get_register(Register::Left),
+                    root.get_register(Register::Right),
+                )
+            };
+            assert!(left != SENTINEL && right != SENTINEL);
+
+            assert_eq!(tree.remove(&root_key), Some(root_key * 2));
+            keys.retain(|key| *key != root_key);
+
+            assert!(tree.is_balanced());
+            assert_eq!(tree.len(), keys.len());
+            assert!(tree.iter().map(|(key, _)| *key).eq(keys.iter().copied()));
+        }
+
+        for key in keys {
+            assert_eq!(tree.remove(&key), Some(key * 2));
+        }
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn test_floor_ceil() {
+        const CAPACITY: usize = 50;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+        assert_eq!(tree.floor(&1), None);
+        assert_eq!(tree.ceil(&1), None);
+
+        // keys with gaps, inserted out of order
+        let mut keys: Vec<u32> = (0..CAPACITY as u32)
+            .map(|i| (i * 7) % 50 * 10 + 5)
+            .collect();
+        for key in &keys {
+            tree.insert(*key, key * 2);
+        }
+        keys.sort();
+
+        for query in 0..520 {
+            let floor = keys.iter().rev().find(|key| **key <= query);
+            let ceil = keys.iter().find(|key| **key >= query);
+
+            assert_eq!(tree.floor(&query).map(|(k, _)| k), floor);
+            assert_eq!(tree.ceil(&query).map(|(k, _)| k), ceil);
+        }
+
+        // exact match returns the entry itself
+        assert_eq!(tree.floor(&15), Some((&15, &30)));
+        assert_eq!(tree.ceil(&15), Some((&15, &30)));
+        // below and above the stored keys
+        assert_eq!(tree.floor(&4), None);
+        assert_eq!(tree.ceil(&4), Some((&5, &10)));
+        assert_eq!(tree.floor(&500), Some((&495, &990)));
+        assert_eq!(tree.ceil(&500), None);
+    }
+
+    #[test]
+    fn test_get_sorted() {
+        const CAPACITY: usize = 50;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        for i in 0..CAPACITY as u32 {
+            let key = (i * 7) % 100;
+            tree.insert(key, key * 10);
+        }
+
+        let keys: Vec<u32> = (0..110).collect();
+        let expected: Vec<Option<u32>> = keys.iter().map(|key| tree.get(key)).collect();
+        let values: Vec<Option<u32>> = tree.get_sorted(&keys).collect();
+
+        assert_eq!(values, expected);
+        assert_eq!(values.iter().flatten().count(), CAPACITY);
+
+        // duplicated keys
+        let values: Vec<Option<u32>> = tree.get_sorted(&[7, 7, 105]).collect();
+        assert_eq!(values, [Some(70), Some(70), None]);
+
+        assert_eq!(tree.get_sorted(&[]).count(), 0);
+    }
+
+    #[test]
+    fn test_reset() {
+        const CAPACITY: usize = 20;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+        let pristine = {
+            let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+            tree.initialize(CAPACITY as u8);
+            data.clone()
+        };
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+
+        for i in 0..CAPACITY as u32 {
+            tree.insert(i, i * 10);
+        }
+        assert!(tree.is_full());
+
+        tree.reset();
+
+        assert!(tree.is_empty());
+        assert_eq!(tree.capacity(), CAPACITY);
+        assert_eq!(tree.get(&1), None);
+
+        // the tree is reusable without reloading
+        for i in 0..CAPACITY as u32 {
+            assert!(tree.insert(i + 100, i).is_some());
+        }
+        assert_eq!(tree.len(), CAPACITY);
+        assert_eq!(tree.get(&105), Some(5));
+
+        tree.reset();
+        assert_eq!(data, pristine);
+    }
+
+    #[test]
+    fn test_compact() {
+        const CAPACITY: usize = 100;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        for i in 0..CAPACITY as u32 {
+            let key = (i * 37) % 100;
+            tree.insert(key, key * 10);
+        }
+        // removes every third key
+        for key in (0..CAPACITY as u32).filter(|key| key % 3 == 0) {
+            tree.remove(&key);
+        }
+        assert!(tree.is_fragmented());
+
+        tree.compact();
+
+        assert!(!tree.is_fragmented());
+        assert_eq!(tree.len(), 66);
+
+        for key in 0..CAPACITY as u32 {
+            if key % 3 == 0 {
+                assert_eq!(tree.get(&key), None);
+            } else {
+                assert_eq!(tree.get(&key), Some(key * 10));
+            }
+        }
+        // nodes are stored in key order
+        let keys: Vec<u32> = tree.nodes[..tree.len()].iter().map(|n| n.key).collect();
+        let expected: Vec<u32> = tree.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, expected);
+
+        // free nodes are used in sequence
+        for (i, key) in (100..134).enumerate() {
+            assert_eq!(tree.insert(key, key), Some(67 + i as u8));
+        }
+        assert!(tree.is_full());
+
+        let keys: Vec<u32> = tree.iter().map(|(k, _)| *k).collect();
+        let expected: Vec<u32> = (0..134).filter(|key| key >= &100 || key % 3 != 0).collect();
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn test_iter_rev() {
+        const CAPACITY: usize = 100;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        assert_eq!(tree.iter_rev().count(), 0);
+
+        for i in 0..CAPACITY as u32 {
+            let key = (i * 37) % 100;
+            tree.insert(key, key * 2);
+        }
+
+        let mut forward: Vec<(&u32, &u32)> = tree.iter().collect();
+        let reverse: Vec<(&u32, &u32)> = tree.iter_rev().collect();
+        forward.reverse();
+
+        assert_eq!(reverse, forward);
+        assert_eq!(reverse[0], (&99, &198));
+
+        // top 3 keys
+        let top: Vec<u32> = tree.iter_rev().take(3).map(|(k, _)| *k).collect();
+        assert_eq!(top, [99, 98, 97]);
+    }
+
+    #[test]
+    fn test_select_rank() {
+        use std::collections::BTreeSet;
+
+        const CAPACITY: usize = 254;
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        let mut reference = BTreeSet::new();
+
+        for i in 0..CAPACITY as u32 {
+            let key = (i * 37) % 254;
+            tree.insert(key, key * 2);
+            reference.insert(key);
+        }
+        // removes some keys to exercise the removal rebalancing
+        for key in (0..CAPACITY as u32).filter(|key| key % 5 == 0) {
+            tree.remove(&key);
+            reference.remove(&key);
+        }
+
+        let assert_order = |tree: &U8AVLTreeMut<u32, u32>, reference: &BTreeSet<u32>| {
+            for (position, key) in reference.iter().enumerate() {
+                assert_eq!(tree.select(position), Some((key, &(key * 2))));
+                assert_eq!(tree.rank(key), Some(position));
+            }
+            assert_eq!(tree.select(reference.len()), None);
+        };
+
+        assert_order(&tree, &reference);
+        assert_eq!(tree.rank(&5), None);
+        assert_eq!(tree.rank(&1000), None);
+
+        // sizes are kept when nodes are reused and after compacting
+        for key in [0, 5, 100] {
+            tree.insert(key, key * 2);
+            reference.insert(key);
+        }
+        assert_order(&tree, &reference);
+
+        tree.compact();
+        assert_order(&tree, &reference);
+
+        // trees written before sizes were stored are detected until compacted
+        tree.nodes
+            .iter_mut()
+            .for_each(|node| node.set_register(Register::Size, 0));
+        assert_eq!(tree.select(0), None);
+        assert_eq!(tree.rank(reference.first().unwrap()), None);
+
+        tree.compact();
+        assert_order(&tree, &reference);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the number of nodes")]
+    fn test_initialize_oversized_capacity() {
+        let mut data = vec![0u8; U8AVLTree::<u32, u32>::data_len(10)];
+
+        let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut data);
+        tree.initialize(11);
+    }
 }
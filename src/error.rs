@@ -0,0 +1,147 @@
+use bytemuck::{checked::CheckedCastError, PodCastError};
+use core::fmt::{Display, Formatter};
+
+/// Errors that can occur when translating bytes into a type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TranslationError {
+    /// The length of the bytes does not match the length required by the type.
+    InvalidLength {
+        /// Number of bytes required (or the maximum number of bytes allowed).
+        expected: usize,
+        /// Number of bytes provided.
+        actual: usize,
+    },
+
+    /// The bytes do not represent a valid value of the type.
+    InvalidValue,
+
+    /// The bytes are not aligned to the alignment required by the type.
+    Misaligned {
+        /// Alignment required by the type.
+        required: usize,
+        /// Alignment of the bytes, i.e., the largest power of two that divides
+        /// their address.
+        actual: usize,
+    },
+
+    /// The operation requires more space than the capacity of the type.
+    CapacityExceeded,
+}
+
+impl Display for TranslationError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TranslationError::InvalidLength { expected, actual } => write!(
+                formatter,
+                "invalid length: expected {} bytes, found {}",
+                expected, actual
+            ),
+            TranslationError::InvalidValue => formatter.write_str("invalid value"),
+            TranslationError::Misaligned { required, actual } => write!(
+                formatter,
+                "misaligned bytes: required alignment {}, found {}",
+                required, actual
+            ),
+            TranslationError::CapacityExceeded => formatter.write_str("capacity exceeded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TranslationError {}
+
+/// Converts a checked cast error of `bytemuck`.
+///
+/// See the conversion from [`PodCastError`] for how the lengths and alignments are
+/// reported.
+impl From<CheckedCastError> for TranslationError {
+    fn from(error: CheckedCastError) -> Self {
+        match error {
+            CheckedCastError::PodCastError(error) => error.into(),
+            CheckedCastError::InvalidBitPattern => TranslationError::InvalidValue,
+        }
+    }
+}
+
+/// Converts a cast error of `bytemuck`.
+///
+/// The error does not carry the lengths or alignments involved in the cast, so the
+/// fields of [`TranslationError::InvalidLength`] and [`TranslationError::Misaligned`]
+/// are set to `0`, meaning unknown.
+impl From<PodCastError> for TranslationError {
+    fn from(error: PodCastError) -> Self {
+        match error {
+            PodCastError::TargetAlignmentGreaterAndInputNotAligned
+            | PodCastError::AlignmentMismatch => TranslationError::Misaligned {
+                required: 0,
+                actual: 0,
+            },
+            PodCastError::OutputSliceWouldHaveSlop | PodCastError::SizeMismatch => {
+                TranslationError::InvalidLength {
+                    expected: 0,
+                    actual: 0,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::{checked::CheckedCastError, PodCastError};
+    use std::string::ToString;
+
+    use crate::TranslationError;
+
+    #[test]
+    fn test_display() {
+        let error = TranslationError::InvalidLength {
+            expected: 8,
+            actual: 3,
+        };
+        assert_eq!(
+            error.to_string(),
+            "invalid length: expected 8 bytes, found 3"
+        );
+
+        let error = TranslationError::Misaligned {
+            required: 4,
+            actual: 2,
+        };
+        assert_eq!(
+            error.to_string(),
+            "misaligned bytes: required alignment 4, found 2"
+        );
+    }
+
+    #[test]
+    fn test_from_cast_errors() {
+        assert_eq!(
+            TranslationError::from(PodCastError::SizeMismatch),
+            TranslationError::InvalidLength {
+                expected: 0,
+                actual: 0
+            }
+        );
+        assert_eq!(
+            TranslationError::from(PodCastError::TargetAlignmentGreaterAndInputNotAligned),
+            TranslationError::Misaligned {
+                required: 0,
+                actual: 0
+            }
+        );
+        assert_eq!(
+            TranslationError::from(CheckedCastError::InvalidBitPattern),
+            TranslationError::InvalidValue
+        );
+        assert_eq!(
+            TranslationError::from(CheckedCastError::PodCastError(
+                PodCastError::OutputSliceWouldHaveSlop
+            )),
+            TranslationError::InvalidLength {
+                expected: 0,
+                actual: 0
+            }
+        );
+    }
+}
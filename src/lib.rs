@@ -1,9 +1,28 @@
-use bytemuck::Pod;
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// the test harness always links `std`, so tests can use it without the feature
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+
+use bytemuck::{checked::CheckedBitPattern, Pod};
+
+mod cast;
 pub mod collections;
+mod error;
 pub mod pod;
 pub mod types;
 
+pub use error::*;
+#[cfg(feature = "derive")]
+pub use stevia_derive::ZeroCopy;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use bytemuck;
+}
+
 /// Trait to represent types with zero-copy deserialization.
 pub trait ZeroCopy
 where
@@ -11,11 +30,413 @@ where
 {
     #[inline]
     fn load(data: &[u8]) -> &Self {
-        bytemuck::from_bytes(&data[..std::mem::size_of::<Self>()])
+        bytemuck::from_bytes(&data[..core::mem::size_of::<Self>()])
     }
 
     #[inline]
     fn load_mut(data: &mut [u8]) -> &mut Self {
-        bytemuck::from_bytes_mut(&mut data[..std::mem::size_of::<Self>()])
+        bytemuck::from_bytes_mut(&mut data[..core::mem::size_of::<Self>()])
+    }
+
+    /// Loads the type from the bytes starting at the specified offset.
+    ///
+    /// Any bytes after the type are ignored, so an offset of `0` reads the type
+    /// from the start of a larger buffer. Returns an error if there are not enough
+    /// bytes after the offset or the bytes at the offset are not aligned for the type.
+    #[inline]
+    fn load_at(data: &[u8], offset: usize) -> Result<&Self, TranslationError> {
+        let end = offset.saturating_add(core::mem::size_of::<Self>());
+        let bytes = data
+            .get(offset..end)
+            .ok_or(TranslationError::InvalidLength {
+                expected: end,
+                actual: data.len(),
+            })?;
+
+        cast::try_from_bytes(bytes)
+    }
+
+    /// Loads a mutable reference to the type from the bytes starting at the
+    /// specified offset.
+    ///
+    /// Returns an error if there are not enough bytes after the offset or the
+    /// bytes at the offset are not aligned for the type.
+    #[inline]
+    fn load_at_mut(data: &mut [u8], offset: usize) -> Result<&mut Self, TranslationError> {
+        let end = offset.saturating_add(core::mem::size_of::<Self>());
+        let actual = data.len();
+        let bytes = data
+            .get_mut(offset..end)
+            .ok_or(TranslationError::InvalidLength {
+                expected: end,
+                actual,
+            })?;
+
+        cast::try_from_bytes_mut(bytes)
+    }
+}
+
+/// Arrays of zero-copy types are also zero-copy, e.g., `[PodBool; N]`.
+///
+/// The array lengths are the ones for which `bytemuck` implements `Pod`.
+impl<T: ZeroCopy, const N: usize> ZeroCopy for [T; N] where [T; N]: Pod {}
+
+/// Macro to implement the `ZeroCopy` trait for primitive integers.
+///
+/// Together with the array implementation, this makes integer arrays zero-copy,
+/// e.g., `[u128; 2]` for wide counters. Note that 128-bit integers require 16-byte
+/// aligned bytes on most platforms.
+macro_rules! zero_copy_integer {
+    ( $($type:ty),+ ) => {
+        $(
+            impl ZeroCopy for $type {}
+        )+
+    };
+}
+
+zero_copy_integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Loads a type that does not accept every bit pattern from the start of the bytes.
+///
+/// This supports types such as `NonZeroU32` or `char`, which cannot be [`ZeroCopy`]
+/// since some values of their bytes are invalid. Any bytes after the type are ignored.
+/// Returns [`TranslationError::InvalidValue`] if the bytes are not a valid value of the
+/// type, or an error if there are not enough bytes or they are not aligned for the type.
+pub fn load_checked<T: CheckedBitPattern>(data: &[u8]) -> Result<&T, TranslationError> {
+    let bytes = data
+        .get(..core::mem::size_of::<T>())
+        .ok_or(TranslationError::InvalidLength {
+            expected: core::mem::size_of::<T>(),
+            actual: data.len(),
+        })?;
+    cast::check_alignment::<T>(bytes)?;
+
+    // the length and alignment are checked, so the only possible error is an
+    // invalid bit pattern
+    bytemuck::checked::try_from_bytes(bytes).map_err(|_| TranslationError::InvalidValue)
+}
+
+/// Splits the bytes into a header and a slice of elements.
+///
+/// The first `size_of::<H>()` bytes are loaded as the header and the remaining
+/// bytes as a slice of `E`. Returns an error if the bytes are not enough for the
+/// header, the remaining length is not a multiple of the size of `E`, or either
+/// part is not aligned for its type.
+pub fn split_load<H: ZeroCopy, E: Pod>(data: &[u8]) -> Result<(&H, &[E]), TranslationError> {
+    let header = H::load_at(data, 0)?;
+    let elements = cast::try_cast_slice(&data[core::mem::size_of::<H>()..])?;
+
+    Ok((header, elements))
+}
+
+/// Splits the bytes into a mutable header and a mutable slice of elements.
+///
+/// See [`split_load`] for the requirements on the bytes.
+pub fn split_load_mut<H: ZeroCopy, E: Pod>(
+    data: &mut [u8],
+) -> Result<(&mut H, &mut [E]), TranslationError> {
+    if data.len() < core::mem::size_of::<H>() {
+        return Err(TranslationError::InvalidLength {
+            expected: core::mem::size_of::<H>(),
+            actual: data.len(),
+        });
+    }
+
+    let (header, elements) = data.split_at_mut(core::mem::size_of::<H>());
+
+    Ok((
+        cast::try_from_bytes_mut(header)?,
+        cast::try_cast_slice_mut(elements)?,
+    ))
+}
+
+/// Asserts at compile time the layout of a [`ZeroCopy`] type.
+///
+/// The assertion fails to compile if the size of the type (and optionally its
+/// alignment) does not match the expected value, which catches changes to the
+/// layout such as accidental padding.
+///
+/// ```
+/// use bytemuck::{Pod, Zeroable};
+/// use stevia::{assert_zero_copy_layout, ZeroCopy};
+///
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Pod, Zeroable)]
+/// struct Record {
+///     id: u32,
+///     amount: u32,
+/// }
+///
+/// impl ZeroCopy for Record {}
+///
+/// assert_zero_copy_layout!(Record, 8);
+/// assert_zero_copy_layout!(Record, 8, 4);
+/// ```
+///
+/// A mismatch fails to compile:
+///
+/// ```compile_fail
+/// use bytemuck::{Pod, Zeroable};
+/// use stevia::{assert_zero_copy_layout, ZeroCopy};
+///
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Pod, Zeroable)]
+/// struct Record {
+///     id: u32,
+///     amount: u32,
+/// }
+///
+/// impl ZeroCopy for Record {}
+///
+/// assert_zero_copy_layout!(Record, 12);
+/// ```
+#[macro_export]
+macro_rules! assert_zero_copy_layout {
+    ( $type:ty, $size:expr ) => {
+        const _: () = {
+            fn assert_zero_copy<T: $crate::ZeroCopy>() {}
+            let _ = assert_zero_copy::<$type>;
+
+            assert!(
+                ::core::mem::size_of::<$type>() == $size,
+                "unexpected size of zero-copy type"
+            );
+        };
+    };
+    ( $type:ty, $size:expr, $align:expr ) => {
+        $crate::assert_zero_copy_layout!($type, $size);
+
+        const _: () = assert!(
+            ::core::mem::align_of::<$type>() == $align,
+            "unexpected alignment of zero-copy type"
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    use bytemuck::{Pod, Zeroable};
+
+    use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8};
+
+    use crate::{load_checked, split_load, split_load_mut, TranslationError, ZeroCopy};
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+    struct Record {
+        id: u32,
+        amount: u32,
+    }
+
+    impl ZeroCopy for Record {}
+
+    assert_zero_copy_layout!(Record, 8, 4);
+    assert_zero_copy_layout!(crate::pod::PodStr<10>, 10, 1);
+
+    #[test]
+    fn test_load_at() {
+        // u64 values to guarantee the alignment of the bytes
+        let mut buffer = [0u64; 3];
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+
+        let record = Record::load_at_mut(data, 8).unwrap();
+        record.id = 1;
+        record.amount = 100;
+
+        assert_eq!(Record::load_at(data, 8), Ok(&Record { id: 1, amount: 100 }));
+        // the record ends at the end of the bytes
+        assert_eq!(Record::load_at(data, 16), Ok(&Record::default()));
+    }
+
+    #[test]
+    fn test_load_at_out_of_bounds() {
+        let mut buffer = [0u64; 2];
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+
+        assert_eq!(
+            Record::load_at(data, 12),
+            Err(TranslationError::InvalidLength {
+                expected: 20,
+                actual: 16
+            })
+        );
+        assert_eq!(
+            Record::load_at(data, 20),
+            Err(TranslationError::InvalidLength {
+                expected: 28,
+                actual: 16
+            })
+        );
+        assert_eq!(
+            Record::load_at_mut(data, usize::MAX).err(),
+            Some(TranslationError::InvalidLength {
+                expected: usize::MAX,
+                actual: 16
+            })
+        );
+        assert_eq!(
+            Record::load_at(data, 12).unwrap_err().to_string(),
+            "invalid length: expected 20 bytes, found 16"
+        );
+    }
+
+    #[test]
+    fn test_load_at_trailing_bytes() {
+        let buffer = [u64::from_le_bytes([1, 0, 0, 0, 100, 0, 0, 0]), u64::MAX];
+        let data: &[u8] = bytemuck::cast_slice(&buffer);
+        let expected = Record { id: 1, amount: 100 };
+
+        // exact length
+        assert_eq!(Record::load_at(&data[..8], 0), Ok(&expected));
+        // trailing bytes are ignored
+        assert_eq!(Record::load_at(data, 0), Ok(&expected));
+        // not enough bytes
+        assert_eq!(
+            Record::load_at(&data[..7], 0),
+            Err(TranslationError::InvalidLength {
+                expected: 8,
+                actual: 7
+            })
+        );
+    }
+
+    #[test]
+    fn test_load_at_misaligned() {
+        let mut buffer = [0u64; 2];
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+
+        assert_eq!(
+            Record::load_at(data, 1),
+            Err(TranslationError::Misaligned {
+                required: 4,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            Record::load_at_mut(data, 6).err(),
+            Some(TranslationError::Misaligned {
+                required: 4,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            Record::load_at(data, 6).unwrap_err().to_string(),
+            "misaligned bytes: required alignment 4, found 2"
+        );
+    }
+
+    #[test]
+    fn test_load_checked() {
+        let buffer = [7u64, 0];
+        let data: &[u8] = bytemuck::cast_slice(&buffer);
+
+        assert_eq!(
+            load_checked::<NonZeroU8>(data),
+            Ok(&NonZeroU8::new(7).unwrap())
+        );
+        assert_eq!(
+            load_checked::<NonZeroU16>(data),
+            Ok(&NonZeroU16::new(7).unwrap())
+        );
+        assert_eq!(
+            load_checked::<NonZeroU32>(data),
+            Ok(&NonZeroU32::new(7).unwrap())
+        );
+        assert_eq!(
+            load_checked::<NonZeroU64>(data),
+            Ok(&NonZeroU64::new(7).unwrap())
+        );
+
+        // zero is not a valid value
+        assert_eq!(
+            load_checked::<NonZeroU64>(&data[8..]),
+            Err(TranslationError::InvalidValue)
+        );
+        assert_eq!(
+            load_checked::<NonZeroU32>(&data[4..]),
+            Err(TranslationError::InvalidValue)
+        );
+        // not enough bytes
+        assert_eq!(
+            load_checked::<NonZeroU32>(&data[..3]),
+            Err(TranslationError::InvalidLength {
+                expected: 4,
+                actual: 3
+            })
+        );
+        // misaligned bytes
+        assert_eq!(
+            load_checked::<NonZeroU32>(&data[1..]),
+            Err(TranslationError::Misaligned {
+                required: 4,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_split_load() {
+        let mut buffer = [0u64; 4];
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+
+        let (header, elements) = split_load_mut::<Record, u32>(data).unwrap();
+        header.id = 1;
+        header.amount = 6;
+        elements.copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+
+        let (header, elements) = split_load::<Record, u32>(data).unwrap();
+        assert_eq!(header, &Record { id: 1, amount: 6 });
+        assert_eq!(elements, [1, 2, 3, 4, 5, 6]);
+
+        // elements can be empty
+        let (_, elements) = split_load::<Record, u64>(&data[..8]).unwrap();
+        assert!(elements.is_empty());
+    }
+
+    #[test]
+    fn test_split_load_invalid() {
+        let mut buffer = [0u64; 4];
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+
+        // the remainder is not a multiple of the element size
+        assert_eq!(
+            split_load::<Record, u64>(&data[..28]).err(),
+            Some(TranslationError::InvalidLength {
+                expected: 16,
+                actual: 20
+            })
+        );
+        assert_eq!(
+            split_load_mut::<Record, u32>(&mut data[..27]).err(),
+            Some(TranslationError::InvalidLength {
+                expected: 16,
+                actual: 19
+            })
+        );
+        // not enough bytes for the header
+        assert_eq!(
+            split_load::<Record, u32>(&data[..4]).err(),
+            Some(TranslationError::InvalidLength {
+                expected: 8,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            split_load_mut::<Record, u32>(&mut data[..4]).err(),
+            Some(TranslationError::InvalidLength {
+                expected: 8,
+                actual: 4
+            })
+        );
+        // misaligned elements
+        assert_eq!(
+            split_load::<Record, u64>(&data[4..]).err(),
+            Some(TranslationError::Misaligned {
+                required: 8,
+                actual: 4
+            })
+        );
     }
 }
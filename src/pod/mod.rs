@@ -1,7 +1,18 @@
+mod ordered_float;
 mod pod_bool;
+mod pod_char;
 mod pod_option;
 mod pod_str;
+mod sentinel;
+mod tuple;
 
+pub use ordered_float::*;
 pub use pod_bool::*;
+pub use pod_char::*;
 pub use pod_option::*;
 pub use pod_str::*;
+pub use sentinel::*;
+pub use tuple::*;
+
+#[cfg(feature = "derive")]
+pub use stevia_derive::Nullable;
@@ -0,0 +1,151 @@
+use bytemuck::{Pod, Zeroable};
+use core::cmp::Ordering;
+
+use crate::ZeroCopy;
+
+/// Macro to implement a totally ordered float type.
+macro_rules! ordered_float {
+    ( $name:tt, $float:ty ) => {
+        #[doc = concat!("A \"pod-enabled\" `", stringify!($float), "` with a total order.")]
+        ///
+        /// Floats only implement `PartialOrd`, since `NaN` values are not comparable,
+        /// so they cannot be used as keys of sorted collections. This type compares
+        #[doc = concat!("values using [`", stringify!($float), "::total_cmp`], which orders negative `NaN`s")]
+        /// first, then negative numbers, `-0.0`, `0.0`, positive numbers and positive
+        /// `NaN`s last. Equality is consistent with this order, so `-0.0` and `0.0`
+        /// are different values.
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Debug, Default, Pod, Zeroable)]
+        pub struct $name(pub $float);
+
+        impl ZeroCopy for $name {}
+
+        impl $name {
+            /// Creates a new ordered float.
+            #[inline]
+            pub fn new(value: $float) -> Self {
+                Self(value)
+            }
+
+            /// Returns the float value.
+            #[inline]
+            pub fn get(&self) -> $float {
+                self.0
+            }
+        }
+
+        impl From<$float> for $name {
+            fn from(value: $float) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $float {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+    };
+}
+
+ordered_float!(OrderedF32, f32);
+ordered_float!(OrderedF64, f64);
+
+#[cfg(test)]
+mod tests {
+    use std::{vec, vec::Vec};
+
+    use crate::{
+        collections::{U32ArraySet, U32ArraySetMut, U64ArraySetMut, U8AVLTree, U8AVLTreeMut},
+        pod::{OrderedF32, OrderedF64},
+    };
+
+    #[test]
+    fn test_array_set() {
+        let mut bytes = vec![0; U64ArraySetMut::<OrderedF64>::data_len(10)];
+        let mut set = U64ArraySetMut::<OrderedF64>::from_bytes_mut(&mut bytes);
+
+        let mut values = [
+            2.5,
+            -1.0,
+            f64::NAN,
+            0.0,
+            -0.0,
+            f64::NEG_INFINITY,
+            10.0,
+            -3.5,
+        ];
+
+        for value in values {
+            assert!(set.insert(value.into()));
+        }
+        // equal values are not inserted twice
+        assert!(!set.insert(2.5.into()));
+        assert!(!set.insert(f64::NAN.into()));
+
+        values.sort_by(f64::total_cmp);
+
+        assert_eq!(set.len(), values.len());
+        assert!(set
+            .iter()
+            .zip(values.iter())
+            .all(|(a, b)| a.get().to_bits() == b.to_bits()));
+        // -0.0 is ordered before 0.0 and NaN last
+        assert_eq!(set[3].get().to_bits(), (-0.0f64).to_bits());
+        assert_eq!(set[4].get().to_bits(), 0.0f64.to_bits());
+        assert!(set[7].get().is_nan());
+
+        assert!(set.contains(&OrderedF64(-3.5)));
+        assert!(!set.contains(&OrderedF64(3.5)));
+
+        let mut bytes = vec![0; U32ArraySetMut::<OrderedF32>::data_len(5)];
+        let mut set = U32ArraySetMut::<OrderedF32>::from_bytes_mut(&mut bytes);
+
+        for value in [1.5f32, -2.0, 0.25] {
+            set.insert(value.into());
+        }
+
+        let set = U32ArraySet::<OrderedF32>::from_bytes(&bytes);
+        let values: Vec<f32> = set.iter().copied().map(f32::from).collect();
+        assert_eq!(values, [-2.0, 0.25, 1.5]);
+    }
+
+    #[test]
+    fn test_avl_tree() {
+        const CAPACITY: usize = 10;
+        let mut data = vec![0u8; U8AVLTree::<OrderedF64, u32>::data_len(CAPACITY)];
+
+        let mut tree = U8AVLTreeMut::<OrderedF64, u32>::from_bytes_mut(&mut data);
+        tree.initialize(CAPACITY as u8);
+
+        for (index, key) in [3.0, -7.5, 0.5, -0.5, 100.0].into_iter().enumerate() {
+            tree.insert(OrderedF64(key), index as u32);
+        }
+
+        let keys: Vec<f64> = tree.iter().map(|(key, _)| key.get()).collect();
+        assert_eq!(keys, [-7.5, -0.5, 0.5, 3.0, 100.0]);
+
+        assert_eq!(tree.get(&OrderedF64(-0.5)), Some(3));
+        assert_eq!(tree.floor(&OrderedF64(1.0)), Some((&OrderedF64(0.5), &2)));
+    }
+}
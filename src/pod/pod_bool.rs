@@ -1,11 +1,27 @@
 use bytemuck::{Pod, Zeroable};
 
-use crate::ZeroCopy;
+use crate::{TranslationError, ZeroCopy};
 
 #[repr(C)]
 #[derive(Copy, Clone, Default, Pod, Zeroable)]
 pub struct PodBool(u8);
 
+impl PodBool {
+    /// Loads a `PodBool` from bytes, requiring the value to be `0` or `1`.
+    ///
+    /// Unlike [`ZeroCopy::load`], which treats any non-zero byte as `true`, this
+    /// returns [`TranslationError::InvalidValue`] for any other byte value.
+    pub fn load_strict(data: &[u8]) -> Result<&Self, TranslationError> {
+        let value = Self::load_at(data, 0)?;
+
+        if value.0 > 1 {
+            return Err(TranslationError::InvalidValue);
+        }
+
+        Ok(value)
+    }
+}
+
 impl From<bool> for PodBool {
     fn from(b: bool) -> Self {
         Self(b.into())
@@ -31,3 +47,41 @@ impl From<PodBool> for bool {
 }
 
 impl ZeroCopy for PodBool {}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use crate::{pod::PodBool, TranslationError, ZeroCopy};
+
+    #[test]
+    fn test_load_strict() {
+        assert!(!bool::from(PodBool::load_strict(&[0]).unwrap()));
+        assert!(bool::from(PodBool::load_strict(&[1]).unwrap()));
+
+        assert_eq!(
+            PodBool::load_strict(&[2]).err(),
+            Some(TranslationError::InvalidValue)
+        );
+        assert_eq!(
+            PodBool::load_strict(&[]).err(),
+            Some(TranslationError::InvalidLength {
+                expected: 1,
+                actual: 0
+            })
+        );
+        // the lenient load accepts any value
+        assert!(bool::from(PodBool::load(&[2])));
+    }
+
+    #[test]
+    fn test_load_array() {
+        let flags = <[PodBool; 4]>::load(&[0, 1, 0, 5]);
+        let flags: Vec<bool> = flags.iter().map(bool::from).collect();
+        assert_eq!(flags, [false, true, false, true]);
+
+        let mut bytes = [0u8; 4];
+        <[PodBool; 4]>::load_mut(&mut bytes)[2] = true.into();
+        assert_eq!(bytes, [0, 0, 1, 0]);
+    }
+}
@@ -0,0 +1,86 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::{TranslationError, ZeroCopy};
+
+/// A "pod-enabled" `char`, stored as its `u32` value.
+///
+/// Not every `u32` is a valid `char` (e.g., surrogates or values above `0x10FFFF`),
+/// so the value is validated when converting it to a `char`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Pod, Zeroable)]
+pub struct PodChar(u32);
+
+impl PodChar {
+    /// Loads a `PodChar` from bytes, requiring the value to be a valid `char`.
+    ///
+    /// Unlike [`ZeroCopy::load`], which accepts any `u32` value, this returns
+    /// [`TranslationError::InvalidValue`] if the value is not a Unicode scalar value.
+    pub fn load_strict(data: &[u8]) -> Result<&Self, TranslationError> {
+        let value = Self::load_at(data, 0)?;
+        char::try_from(value)?;
+
+        Ok(value)
+    }
+}
+
+impl From<char> for PodChar {
+    fn from(c: char) -> Self {
+        Self(c.into())
+    }
+}
+
+impl TryFrom<&PodChar> for char {
+    type Error = TranslationError;
+
+    fn try_from(c: &PodChar) -> Result<Self, Self::Error> {
+        char::from_u32(c.0).ok_or(TranslationError::InvalidValue)
+    }
+}
+
+impl TryFrom<PodChar> for char {
+    type Error = TranslationError;
+
+    fn try_from(c: PodChar) -> Result<Self, Self::Error> {
+        char::try_from(&c)
+    }
+}
+
+impl ZeroCopy for PodChar {}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::bytes_of;
+
+    use crate::{pod::PodChar, TranslationError, ZeroCopy};
+
+    #[test]
+    fn test_load_strict() {
+        for c in ['a', 'ß', '\u{D7FF}', '\u{E000}', '🦀', char::MAX] {
+            let value = u32::from(c);
+            let value = PodChar::load_strict(bytes_of(&value)).unwrap();
+            assert_eq!(char::try_from(value), Ok(c));
+        }
+
+        // surrogates and values out of range
+        for invalid in [0xD800u32, 0xDFFF, 0x110000, u32::MAX] {
+            let bytes = bytes_of(&invalid);
+            assert_eq!(
+                PodChar::load_strict(bytes).err(),
+                Some(TranslationError::InvalidValue)
+            );
+            // the lenient load accepts any value
+            assert_eq!(
+                char::try_from(PodChar::load(bytes)),
+                Err(TranslationError::InvalidValue)
+            );
+        }
+
+        assert_eq!(
+            PodChar::load_strict(&[0; 3]).err(),
+            Some(TranslationError::InvalidLength {
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
+}
@@ -11,6 +11,31 @@ pub trait Nullable: Pod {
     fn is_none(&self) -> bool;
 }
 
+/// Macro to implement the `Nullable` trait for integer arrays, where an
+/// all-zero array represents `None`.
+macro_rules! nullable_array {
+    ( $($type:ty),+ ) => {
+        $(
+            impl<const N: usize> Nullable for [$type; N]
+            where
+                [$type; N]: Pod,
+            {
+                #[inline]
+                fn is_some(&self) -> bool {
+                    self.iter().any(|value| *value != 0)
+                }
+
+                #[inline]
+                fn is_none(&self) -> bool {
+                    !self.is_some()
+                }
+            }
+        )+
+    };
+}
+
+nullable_array!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
 /// A "pod-enabled" type that can be used as an `Option<T>` without
 /// requiring extra space to indicate if the value is `Some` or `None`.
 ///
@@ -41,6 +66,22 @@ impl<T: Nullable> PodOption<T> {
         }
     }
 
+    /// Returns a reference to the wrapped value, regardless of whether it represents
+    /// `None`.
+    #[inline]
+    pub fn raw(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns the wrapped value, regardless of whether it represents `None`.
+    ///
+    /// Unlike [`Self::value`], this bypasses the `Option` semantics, so the returned
+    /// value may be the one that represents `None`.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
     #[inline]
     pub fn value_mut(&mut self) -> Option<&mut T> {
         if self.0.is_some() {
@@ -50,3 +91,133 @@ impl<T: Nullable> PodOption<T> {
         }
     }
 }
+
+/// Returns an iterator over the `Some` values of a slice of [`PodOption`].
+///
+/// Elements holding a `None` value are skipped.
+pub fn iter_present<T: Nullable>(slice: &[PodOption<T>]) -> impl Iterator<Item = &T> {
+    slice.iter().filter_map(|option| option.value())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use bytemuck::{Pod, Zeroable};
+
+    use crate::{
+        pod::{iter_present, Nullable, PodOption},
+        ZeroCopy,
+    };
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Pod, Zeroable)]
+    struct Id(u64);
+
+    impl Nullable for Id {
+        fn is_some(&self) -> bool {
+            self.0 != 0
+        }
+
+        fn is_none(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    #[test]
+    fn test_iter_present() {
+        let options = [
+            PodOption::new(Id(1)),
+            PodOption::new(Id(0)),
+            PodOption::new(Id(2)),
+            PodOption::new(Id(0)),
+            PodOption::new(Id(3)),
+        ];
+
+        let present: Vec<&Id> = iter_present(&options).collect();
+
+        assert_eq!(present.len(), 3);
+        assert_eq!(present, [&Id(1), &Id(2), &Id(3)]);
+    }
+
+    #[test]
+    fn test_partial_eq_only() {
+        // f64 is only PartialEq, so PodOption<Price> is not Eq or Hash
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+        struct Price(f64);
+
+        impl Nullable for Price {
+            fn is_some(&self) -> bool {
+                !self.0.is_nan()
+            }
+
+            fn is_none(&self) -> bool {
+                self.0.is_nan()
+            }
+        }
+
+        let price = PodOption::new(Price(1.5));
+        assert_eq!(price.value(), Some(&Price(1.5)));
+        assert_eq!(price, PodOption::new(Price(1.5)));
+
+        let price = PodOption::new(Price(f64::NAN));
+        assert!(price.value().is_none());
+    }
+
+    #[test]
+    fn test_into_inner() {
+        let option = PodOption::new(Id(0));
+        assert!(option.value().is_none());
+        // the raw value is the one representing none
+        assert_eq!(option.raw(), &Id(0));
+        assert_eq!(option.into_inner(), Id(0));
+
+        let option = PodOption::new(Id(7));
+        assert_eq!(option.raw(), &Id(7));
+        assert_eq!(option.into_inner(), Id(7));
+    }
+
+    #[test]
+    fn test_wide_arrays() {
+        // the buffer is a u128 array to guarantee the 16-byte alignment
+        let mut buffer = [0u128; 2];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+
+        let counters = <[u128; 2]>::load_mut(bytes);
+        *counters = [1, u128::MAX];
+        assert_eq!(<[u128; 2]>::load(bytes), &[1, u128::MAX]);
+        assert_eq!(buffer, [1, u128::MAX]);
+
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+        <[i128; 2]>::load_mut(bytes)[1] = i128::MIN;
+        assert_eq!(<[i128; 2]>::load(bytes), &[1, i128::MIN]);
+
+        // an all-zero array represents none
+        let option = PodOption::new([0u128; 2]);
+        assert!(option.value().is_none());
+
+        let option = PodOption::new([0, 5u128]);
+        assert_eq!(option.value(), Some(&[0, 5]));
+
+        let option = PodOption::new([0, -1i128]);
+        assert_eq!(option.value(), Some(&[0, -1]));
+        assert!(PodOption::new([0i128; 3]).value().is_none());
+    }
+
+    #[test]
+    fn test_integer_arrays() {
+        let mut buffer = [0u64; 2];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+
+        <[u32; 4]>::load_mut(bytes)[3] = u32::MAX;
+        assert_eq!(<[u32; 4]>::load(bytes), &[0, 0, 0, u32::MAX]);
+        assert_eq!(<[i16; 8]>::load(bytes)[7], -1);
+        assert_eq!(u64::load(bytes), &0);
+
+        assert!(PodOption::new([0u8; 32]).value().is_none());
+        assert_eq!(PodOption::new([0, 1u16]).value(), Some(&[0, 1]));
+        assert_eq!(PodOption::new([-1i32, 0]).value(), Some(&[-1, 0]));
+        assert!(PodOption::new([0i64; 2]).value().is_none());
+    }
+}
@@ -1,12 +1,15 @@
-use crate::ZeroCopy;
+use crate::{TranslationError, ZeroCopy};
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
 use bytemuck::{Pod, Zeroable};
-use std::fmt::{Debug, Display};
-use std::str;
-use std::str::Utf8Error;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display, Write};
+use core::str;
+use core::str::Utf8Error;
 
 /// Struct representing a "pod-enabled" `str`.
 #[repr(C)]
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, Debug)]
 pub struct PodStr<const MAX_SIZE: usize> {
     /// The bytes of the string.
     pub value: [u8; MAX_SIZE],
@@ -14,24 +17,134 @@ pub struct PodStr<const MAX_SIZE: usize> {
 
 impl<const MAX_SIZE: usize> PodStr<MAX_SIZE> {
     pub fn copy_from_slice(&mut self, slice: &[u8]) {
-        let length = std::cmp::min(slice.len(), MAX_SIZE);
+        let length = core::cmp::min(slice.len(), MAX_SIZE);
         self.value[..length].clone_from_slice(&slice[..length]);
         self.value[length..].fill(0);
     }
 
+    /// Creates a pod str from a `&str`, returning an error if the string does not fit.
+    ///
+    /// Unlike the `From<&str>` implementation, which truncates strings longer than
+    /// `MAX_SIZE`, this returns [`TranslationError::InvalidLength`] in that case.
+    pub fn try_from_str(s: &str) -> Result<Self, TranslationError> {
+        if s.len() > MAX_SIZE {
+            return Err(TranslationError::InvalidLength {
+                expected: MAX_SIZE,
+                actual: s.len(),
+            });
+        }
+
+        Ok(Self::from(s))
+    }
+
+    /// Copies the content of a `&str` into the pod str, returning an error if the
+    /// string does not fit.
+    ///
+    /// Unlike [`Self::copy_from_str`], which truncates strings longer than `MAX_SIZE`,
+    /// this returns [`TranslationError::InvalidLength`] in that case and leaves the
+    /// pod str unchanged.
+    pub fn try_copy_from_str(&mut self, string: &str) -> Result<(), TranslationError> {
+        if string.len() > MAX_SIZE {
+            return Err(TranslationError::InvalidLength {
+                expected: MAX_SIZE,
+                actual: string.len(),
+            });
+        }
+
+        self.copy_from_str(string);
+        Ok(())
+    }
+
+    /// Clears the pod str, filling all its bytes with zeros.
+    pub fn clear(&mut self) {
+        self.value.fill(0);
+    }
+
     /// Copy the content of a `&str` into the pod str.
     pub fn copy_from_str(&mut self, string: &str) {
         self.copy_from_slice(string.as_bytes())
     }
 
-    /// Tries to convert to a `&str` if it is valid UTF-8. Behaves like [`str::from_utf8`].
-    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+    /// Returns the bytes of the string up to the first null byte.
+    ///
+    /// If there is no null byte, all `MAX_SIZE` bytes are returned.
+    pub fn as_bytes(&self) -> &[u8] {
         let end_index = self
             .value
             .iter()
             .position(|&x| x == b'\0')
             .unwrap_or(MAX_SIZE);
-        str::from_utf8(&self.value[..end_index])
+        &self.value[..end_index]
+    }
+
+    /// Returns the length of the string in bytes, i.e., the number of bytes up to the
+    /// first null byte.
+    pub fn byte_len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Returns the number of bytes occupied by the pod str, which is always `MAX_SIZE`
+    /// regardless of the length of the string.
+    pub const fn size(&self) -> usize {
+        MAX_SIZE
+    }
+
+    /// Returns a reference to the full backing array, including any trailing null bytes.
+    pub fn as_array(&self) -> &[u8; MAX_SIZE] {
+        &self.value
+    }
+
+    /// Returns a mutable reference to the full backing array.
+    ///
+    /// This allows editing the bytes in place. The string ends at the first null
+    /// byte, so callers must keep the bytes up to it valid UTF-8 (and write a null
+    /// byte after the content when it is shorter than `MAX_SIZE`) for
+    /// [`as_str`](Self::as_str) to succeed.
+    pub fn as_mut_bytes(&mut self) -> &mut [u8; MAX_SIZE] {
+        &mut self.value
+    }
+
+    /// Returns `true` if the string starts with the specified prefix.
+    ///
+    /// Only the bytes up to the first null byte are considered, and they are not
+    /// required to be valid UTF-8.
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.as_bytes().starts_with(prefix.as_bytes())
+    }
+
+    /// Returns `true` if the string ends with the specified suffix.
+    ///
+    /// Only the bytes up to the first null byte are considered, and they are not
+    /// required to be valid UTF-8.
+    pub fn ends_with(&self, suffix: &str) -> bool {
+        self.as_bytes().ends_with(suffix.as_bytes())
+    }
+
+    /// Tries to convert to a `&str` if it is valid UTF-8. Behaves like [`str::from_utf8`].
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        str::from_utf8(self.as_bytes())
+    }
+
+    /// Converts to a `&str`, requiring the string to be null-terminated.
+    ///
+    /// Unlike [`as_str`](Self::as_str), which treats all `MAX_SIZE` bytes as the string
+    /// when there is no null byte, this returns [`TranslationError::InvalidValue`] in
+    /// that case or if the bytes are not valid UTF-8.
+    pub fn as_str_terminated(&self) -> Result<&str, TranslationError> {
+        if !self.value.contains(&b'\0') {
+            return Err(TranslationError::InvalidValue);
+        }
+
+        self.as_str().map_err(|_| TranslationError::InvalidValue)
+    }
+
+    /// Converts to a string, replacing invalid UTF-8 sequences with the replacement
+    /// character. Behaves like [`String::from_utf8_lossy`].
+    ///
+    /// Only the bytes up to the first null byte are converted.
+    #[cfg(feature = "alloc")]
+    pub fn as_str_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.as_bytes())
     }
 
     /// Converts to a `&str` without checking if it is valid UTF-8.
@@ -40,12 +153,7 @@ impl<const MAX_SIZE: usize> PodStr<MAX_SIZE> {
     /// The caller must guarantee that the bytes are valid UTF-8. This has the same safety requirements
     /// as [`str::from_utf8_unchecked`].
     pub unsafe fn as_str_unchecked(&self) -> &str {
-        let end_index = self
-            .value
-            .iter()
-            .position(|&x| x == b'\0')
-            .unwrap_or(MAX_SIZE);
-        unsafe { str::from_utf8_unchecked(&self.value[..end_index]) }
+        unsafe { str::from_utf8_unchecked(self.as_bytes()) }
     }
 }
 
@@ -63,34 +171,126 @@ impl<const MAX_SIZE: usize> Default for PodStr<MAX_SIZE> {
     }
 }
 
+// comparisons only consider the bytes up to the first null byte, so trailing bytes
+// after it do not affect the equality or the order of strings
+impl<const MAX_SIZE: usize> PartialEq for PodStr<MAX_SIZE> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const MAX_SIZE: usize> Eq for PodStr<MAX_SIZE> {}
+
+impl<const MAX_SIZE: usize> PartialOrd for PodStr<MAX_SIZE> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const MAX_SIZE: usize> Ord for PodStr<MAX_SIZE> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
 impl<const MAX_SIZE: usize> Display for PodStr<MAX_SIZE> {
-    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = String::from_utf8_lossy(&self.value);
-        formatter.write_str(&str)
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut bytes = &self.value[..];
+        // writes the valid UTF-8 sequences, replacing the invalid ones with the
+        // replacement character (same as `String::from_utf8_lossy`)
+        loop {
+            match str::from_utf8(bytes) {
+                Ok(valid) => return formatter.write_str(valid),
+                Err(error) => {
+                    let (valid, invalid) = bytes.split_at(error.valid_up_to());
+                    // safety: the bytes up to `valid_up_to` are valid UTF-8
+                    formatter.write_str(unsafe { str::from_utf8_unchecked(valid) })?;
+                    formatter.write_char(char::REPLACEMENT_CHARACTER)?;
+
+                    if let Some(length) = error.error_len() {
+                        bytes = &invalid[length..];
+                    } else {
+                        return Ok(());
+                    }
+                }
+            }
+        }
     }
 }
 
+/// Creates a pod str from a `&str`.
+///
+/// Strings longer than `MAX_SIZE` are truncated; use [`PodStr::try_from_str`] to
+/// get an error instead.
 impl<const MAX_SIZE: usize> From<&str> for PodStr<MAX_SIZE> {
     fn from(s: &str) -> Self {
         let mut value = [0; MAX_SIZE];
-        let length = std::cmp::min(s.len(), MAX_SIZE);
+        let length = core::cmp::min(s.len(), MAX_SIZE);
         value[..length].clone_from_slice(&s.as_bytes()[..length]);
         Self { value }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<const MAX_SIZE: usize> From<String> for PodStr<MAX_SIZE> {
     fn from(s: String) -> Self {
         s.as_str().into()
     }
 }
 
+impl<const MAX_SIZE: usize> From<[u8; MAX_SIZE]> for PodStr<MAX_SIZE> {
+    fn from(value: [u8; MAX_SIZE]) -> Self {
+        Self { value }
+    }
+}
+
+impl<const MAX_SIZE: usize> AsRef<[u8]> for PodStr<MAX_SIZE> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const MAX_SIZE: usize> serde::Serialize for PodStr<MAX_SIZE> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = self.as_str().map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const MAX_SIZE: usize> serde::Deserialize<'de> for PodStr<MAX_SIZE> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PodStrVisitor<const MAX_SIZE: usize>;
+
+        impl<'de, const MAX_SIZE: usize> serde::de::Visitor<'de> for PodStrVisitor<MAX_SIZE> {
+            type Value = PodStr<MAX_SIZE>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(formatter, "a string with at most {} bytes", MAX_SIZE)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                if value.len() > MAX_SIZE {
+                    return Err(E::invalid_length(value.len(), &self));
+                }
+
+                Ok(PodStr::from(value))
+            }
+        }
+
+        deserializer.deserialize_str(PodStrVisitor::<MAX_SIZE>)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::{string::String, string::ToString};
+
     use bytemuck::bytes_of;
     use std::str::Utf8Error;
 
-    use crate::{pod::PodStr, ZeroCopy};
+    use crate::{pod::PodStr, TranslationError, ZeroCopy};
 
     type Result = std::result::Result<(), Utf8Error>;
 
@@ -111,6 +311,38 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_as_str_lossy() -> Result {
+        let str = PodStr::<10>::from("str");
+        assert_eq!(str.as_str_lossy(), "str");
+
+        // Invalid utf-8 bytes. The fourth byte has to be 10xxxxxx.
+        let invalid_bits: [u8; 4] = [0b1111_0000, 0b1100_0000, 0b1100_0000, 1];
+        let mut str = PodStr::<10>::default();
+        str.copy_from_slice(&invalid_bits);
+
+        let lossy = str.as_str_lossy();
+        assert!(lossy.contains(char::REPLACEMENT_CHARACTER));
+        assert!(lossy.ends_with('\u{1}'));
+        Ok(())
+    }
+
+    #[test]
+    fn test_display() -> Result {
+        let str = PodStr::<4>::from("str");
+        assert_eq!(str.to_string(), "str\0");
+
+        // Invalid utf-8 bytes. The fourth byte has to be 10xxxxxx.
+        let invalid_bits: [u8; 4] = [0b1111_0000, 0b1100_0000, 0b1100_0000, 1];
+        let str = PodStr::<4>::from(invalid_bits);
+        assert_eq!(
+            str.to_string(),
+            String::from_utf8_lossy(&invalid_bits).into_owned()
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_copy_from_slice() -> Result {
         let mut str = PodStr::<10>::from("empty");
@@ -130,6 +362,17 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_from_array() -> Result {
+        let mut bytes = [0u8; 10];
+        bytes[..3].copy_from_slice(b"str");
+
+        let str = PodStr::<10>::from(bytes);
+        assert_eq!(str.as_str()?, "str");
+        assert_eq!(str.as_ref(), b"str");
+        Ok(())
+    }
+
     #[test]
     fn test_load() -> Result {
         let str = PodStr::<10>::from("str");
@@ -141,4 +384,163 @@ mod tests {
         assert_eq!(&str, loaded);
         Ok(())
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() -> Result {
+        let str = PodStr::<10>::from("str");
+
+        let json = serde_json::to_string(&str).unwrap();
+        assert_eq!(json, "\"str\"");
+
+        let deserialized: PodStr<10> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, str);
+        assert_eq!(deserialized.as_str()?, "str");
+
+        // the string does not fit in the pod str
+        assert!(serde_json::from_str::<PodStr<2>>(&json).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_str() -> Result {
+        // exactly fits
+        let str = PodStr::<3>::try_from_str("str").unwrap();
+        assert_eq!(str.as_str()?, "str");
+
+        // shorter
+        let str = PodStr::<10>::try_from_str("str").unwrap();
+        assert_eq!(str.as_str()?, "str");
+
+        // overflows
+        assert_eq!(
+            PodStr::<2>::try_from_str("str"),
+            Err(TranslationError::InvalidLength {
+                expected: 2,
+                actual: 3
+            })
+        );
+        // while the conversion truncates
+        assert_eq!(PodStr::<2>::from("str").as_str()?, "st");
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_copy_from_str() -> Result {
+        let mut str = PodStr::<3>::default();
+
+        str.try_copy_from_str("str").unwrap();
+        assert_eq!(str.as_str()?, "str");
+
+        str.try_copy_from_str("s").unwrap();
+        assert_eq!(str.as_str()?, "s");
+
+        // overflows, leaving the previous value
+        assert_eq!(
+            str.try_copy_from_str("string"),
+            Err(TranslationError::InvalidLength {
+                expected: 3,
+                actual: 6
+            })
+        );
+        assert_eq!(str.as_str()?, "s");
+        Ok(())
+    }
+
+    #[test]
+    fn test_size() {
+        let str = PodStr::<10>::from("str");
+        assert_eq!(str.size(), 10);
+        assert_eq!(str.byte_len(), 3);
+
+        let str = PodStr::<10>::default();
+        assert_eq!(str.size(), 10);
+        assert_eq!(str.byte_len(), 0);
+
+        let str = PodStr::<3>::from("string");
+        assert_eq!(str.size(), 3);
+        assert_eq!(str.byte_len(), 3);
+        assert_eq!(str.size(), core::mem::size_of::<PodStr<3>>());
+    }
+
+    #[test]
+    fn test_starts_ends_with() {
+        let str = PodStr::<10>::from("prefix");
+
+        assert!(str.starts_with("pre"));
+        assert!(str.starts_with(""));
+        assert!(!str.starts_with("fix"));
+
+        assert!(str.ends_with("fix"));
+        assert!(str.ends_with("prefix"));
+        assert!(!str.ends_with("pre"));
+        // the suffix includes bytes after the null boundary
+        assert!(!str.ends_with("fix\0"));
+        assert!(!str.starts_with("prefix\0\0\0\0"));
+    }
+
+    #[test]
+    fn test_as_array() {
+        let str = PodStr::<10>::from("str");
+        let array: &[u8; 10] = str.as_array();
+
+        assert_eq!(array.len(), 10);
+        assert_eq!(array, b"str\0\0\0\0\0\0\0");
+        assert_eq!(&array[..str.as_bytes().len()], str.as_bytes());
+    }
+
+    #[test]
+    fn test_clear() -> Result {
+        let mut str = PodStr::<10>::from("string");
+        assert_eq!(str.as_str()?, "string");
+
+        str.clear();
+
+        assert_eq!(str.as_str()?, "");
+        assert_eq!(str.value, [0; 10]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_as_str_terminated() {
+        let str = PodStr::<10>::from("string");
+        assert_eq!(str.as_str_terminated(), Ok("string"));
+
+        // the buffer is filled without a null terminator
+        let str = PodStr::<6>::from("string");
+        assert_eq!(str.as_str(), Ok("string"));
+        assert_eq!(str.as_str_terminated(), Err(TranslationError::InvalidValue));
+
+        let mut str = PodStr::<10>::default();
+        str.copy_from_slice(&[0b1111_0000, 0b1100_0000]);
+        assert_eq!(str.as_str_terminated(), Err(TranslationError::InvalidValue));
+    }
+
+    #[test]
+    fn test_as_mut_bytes() -> Result {
+        let mut str = PodStr::<10>::from("string");
+
+        str.as_mut_bytes()[0] = b'S';
+        assert_eq!(str.as_str()?, "String");
+
+        // truncates the string
+        str.as_mut_bytes()[3] = 0;
+        assert_eq!(str.as_str()?, "Str");
+
+        str.as_mut_bytes()[1] = 0xFF;
+        assert!(str.as_str().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut str = PodStr::<10>::from("str");
+        // bytes after the null terminator are ignored
+        str.value[5] = b'x';
+        assert_eq!(str, PodStr::<10>::from("str"));
+
+        assert!(PodStr::<10>::from("a") < PodStr::<10>::from("b"));
+        assert!(PodStr::<10>::from("str") < PodStr::<10>::from("string"));
+        assert!(PodStr::<10>::default() < PodStr::<10>::from("a"));
+    }
 }
@@ -0,0 +1,93 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::{pod::Nullable, ZeroCopy};
+
+/// A "pod-enabled" wrapper that represents `None` as a value with all its
+/// bytes set to `BYTE`.
+///
+/// This can be used with [`PodOption`](crate::pod::PodOption) when the `None`
+/// value of a type is not all zeros, e.g., `PodOption<Sentinel<[u8; 32], 0xFF>>`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Sentinel<T: Pod, const BYTE: u8>(T);
+
+unsafe impl<T: Pod, const BYTE: u8> Pod for Sentinel<T, BYTE> {}
+
+unsafe impl<T: Pod, const BYTE: u8> Zeroable for Sentinel<T, BYTE> {}
+
+impl<T: Pod, const BYTE: u8> ZeroCopy for Sentinel<T, BYTE> {}
+
+impl<T: Pod, const BYTE: u8> Sentinel<T, BYTE> {
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns a value with all its bytes set to `BYTE`.
+    #[inline]
+    pub fn none() -> Self {
+        let mut value = T::zeroed();
+        bytemuck::bytes_of_mut(&mut value).fill(BYTE);
+        Self(value)
+    }
+
+    #[inline]
+    pub fn value(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Pod, const BYTE: u8> Nullable for Sentinel<T, BYTE> {
+    #[inline]
+    fn is_some(&self) -> bool {
+        !self.is_none()
+    }
+
+    #[inline]
+    fn is_none(&self) -> bool {
+        bytemuck::bytes_of(&self.0).iter().all(|byte| *byte == BYTE)
+    }
+}
+
+impl<T: Pod, const BYTE: u8> From<T> for Sentinel<T, BYTE> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pod::{PodOption, Sentinel},
+        ZeroCopy,
+    };
+
+    #[test]
+    fn test_zero_sentinel() {
+        let option = PodOption::new(Sentinel::<[u8; 32], 0>::none());
+        assert!(option.value().is_none());
+
+        let option = PodOption::new(Sentinel::<[u8; 32], 0>::new([0xFF; 32]));
+        assert_eq!(option.value().map(|v| v.value()), Some(&[0xFF; 32]));
+    }
+
+    #[test]
+    fn test_max_sentinel() {
+        let option = PodOption::new(Sentinel::<[u8; 32], 0xFF>::none());
+        assert!(option.value().is_none());
+
+        // all zeros is a valid value
+        let option = PodOption::new(Sentinel::<[u8; 32], 0xFF>::new([0; 32]));
+        assert_eq!(option.value().map(|v| v.value()), Some(&[0; 32]));
+
+        let mut key = [0xFF; 32];
+        key[31] = 0xFE;
+        let option = PodOption::new(Sentinel::<[u8; 32], 0xFF>::new(key));
+        assert!(option.value().is_some());
+
+        // loads the option from bytes
+        let bytes = [0xFFu8; 32];
+        let option = PodOption::<Sentinel<[u8; 32], 0xFF>>::load(&bytes);
+        assert!(option.value().is_none());
+    }
+}
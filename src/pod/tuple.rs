@@ -0,0 +1,124 @@
+use bytemuck::{Pod, Zeroable};
+use core::fmt::Debug;
+
+use crate::ZeroCopy;
+
+/// Macro to implement a "pod-enabled" tuple type.
+macro_rules! pod_tuple {
+    ( $name:tt, $arity:literal, $( ($type:tt, $index:tt) ),+ ) => {
+        #[doc = concat!("A \"pod-enabled\" tuple of ", $arity, " values.")]
+        ///
+        /// Tuples do not have a defined layout, so they cannot be used in zero-copy
+        /// types. This type stores the values in the declared order without any padding
+        /// between them (`repr(C, packed)`), so its size is the sum of the sizes of the
+        /// values and its alignment is `1`. Since the values might be unaligned, they
+        /// can only be read and written by value, e.g., `let first = pair.0;`.
+        #[repr(C, packed)]
+        #[derive(Default)]
+        pub struct $name<$( $type ),+>($( pub $type ),+);
+
+        // manual implementations since the values of a packed struct can only be
+        // copied
+        impl<$( $type: Copy ),+> Clone for $name<$( $type ),+> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<$( $type: Copy ),+> Copy for $name<$( $type ),+> {}
+
+        unsafe impl<$( $type: Pod ),+> Pod for $name<$( $type ),+> {}
+
+        unsafe impl<$( $type: Zeroable ),+> Zeroable for $name<$( $type ),+> {}
+
+        impl<$( $type: Pod ),+> ZeroCopy for $name<$( $type ),+> {}
+
+        impl<$( $type: Copy ),+> $name<$( $type ),+> {
+            #[inline]
+            pub fn new($( $index: $type ),+) -> Self {
+                Self($( $index ),+)
+            }
+        }
+
+        impl<$( $type: Copy ),+> From<($( $type ),+)> for $name<$( $type ),+> {
+            fn from(($( $index ),+): ($( $type ),+)) -> Self {
+                Self($( $index ),+)
+            }
+        }
+
+        impl<$( $type: Copy ),+> From<$name<$( $type ),+>> for ($( $type ),+) {
+            fn from(tuple: $name<$( $type ),+>) -> Self {
+                let $name($( $index ),+) = tuple;
+                ($( $index ),+)
+            }
+        }
+
+        impl<$( $type: Copy + PartialEq ),+> PartialEq for $name<$( $type ),+> {
+            fn eq(&self, other: &Self) -> bool {
+                <($( $type ),+)>::from(*self) == <($( $type ),+)>::from(*other)
+            }
+        }
+
+        impl<$( $type: Copy + Eq ),+> Eq for $name<$( $type ),+> {}
+
+        impl<$( $type: Copy + Debug ),+> Debug for $name<$( $type ),+> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let $name($( $index ),+) = *self;
+                f.debug_tuple(stringify!($name))
+                    $( .field(&$index) )+
+                    .finish()
+            }
+        }
+    };
+}
+
+pod_tuple!(Pair, 2, (A, a), (B, b));
+pod_tuple!(Triple, 3, (A, a), (B, b), (C, c));
+
+#[cfg(test)]
+mod tests {
+    use std::format;
+
+    use core::mem::{align_of, size_of};
+
+    use crate::{
+        pod::{Pair, Triple},
+        ZeroCopy,
+    };
+
+    #[test]
+    fn test_pair() {
+        // no padding between or after the values
+        assert_eq!(size_of::<Pair<u32, u16>>(), 6);
+        assert_eq!(align_of::<Pair<u32, u16>>(), 1);
+
+        let mut bytes = [0u8; 7];
+        bytes[1..5].copy_from_slice(&100u32.to_ne_bytes());
+        bytes[5..7].copy_from_slice(&7u16.to_ne_bytes());
+
+        // the values can be loaded at any offset
+        let pair = Pair::<u32, u16>::load(&bytes[1..]);
+        assert_eq!({ pair.0 }, 100);
+        assert_eq!({ pair.1 }, 7);
+        assert_eq!(<(u32, u16)>::from(*pair), (100, 7));
+
+        let pair = Pair::<u32, u16>::load_mut(&mut bytes[1..]);
+        pair.1 = 8;
+        assert_eq!(bytes[5..7], 8u16.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_triple() {
+        assert_eq!(size_of::<Triple<u8, u64, u16>>(), 11);
+
+        let triple = Triple::new(1u8, 2u64, 3u16);
+        let bytes = bytemuck::bytes_of(&triple);
+        assert_eq!(bytes[0], 1);
+        assert_eq!(bytes[1..9], 2u64.to_ne_bytes());
+        assert_eq!(bytes[9..], 3u16.to_ne_bytes());
+
+        assert_eq!(Triple::<u8, u64, u16>::load(bytes), &triple);
+        assert_eq!(Triple::from((1u8, 2u64, 3u16)), triple);
+        assert_eq!(format!("{triple:?}"), "Triple(1, 2, 3)");
+    }
+}
@@ -0,0 +1,69 @@
+use core::ops::{Deref, DerefMut};
+
+/// A zeroed byte buffer aligned to 16 bytes.
+///
+/// The alignment is enough for the types stored in the collections, so the buffer
+/// can be used as their backing storage without alignment errors when casting the
+/// bytes. See [`buffer!`](crate::buffer) to create a buffer for a collection.
+#[repr(C, align(16))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AlignedBuffer<const N: usize>([u8; N]);
+
+impl<const N: usize> AlignedBuffer<N> {
+    /// Creates a zeroed buffer.
+    pub const fn new() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<const N: usize> Default for AlignedBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for AlignedBuffer<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> DerefMut for AlignedBuffer<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Creates a zeroed [`AlignedBuffer`] with the data length required by a collection
+/// with the specified capacity.
+///
+/// The collection type must have a `const fn data_len(capacity: usize) -> usize`.
+///
+/// ```
+/// use stevia::{buffer, collections::U32ArraySetMut};
+///
+/// let mut buffer = buffer!(U32ArraySetMut<u32>, 10);
+/// let mut set = U32ArraySetMut::<u32>::from_bytes_mut(&mut buffer);
+///
+/// assert!(set.insert(1));
+/// assert_eq!(set.capacity(), 10);
+/// ```
+///
+/// ```
+/// use stevia::{buffer, collections::U8AVLTreeMut};
+///
+/// let mut buffer = buffer!(U8AVLTreeMut<u32, u32>, 10);
+/// let mut tree = U8AVLTreeMut::<u32, u32>::from_bytes_mut(&mut buffer);
+/// tree.initialize(10);
+///
+/// assert!(tree.insert(1, 10).is_some());
+/// assert_eq!(tree.get(&1), Some(10));
+/// ```
+#[macro_export]
+macro_rules! buffer {
+    ( $type:ty, $capacity:expr ) => {
+        $crate::types::AlignedBuffer::<{ <$type>::data_len($capacity) }>::new()
+    };
+}
@@ -1,3 +1,7 @@
+mod buffer;
+mod prefix;
 mod prefix_str;
 
+pub use buffer::*;
+pub use prefix::*;
 pub use prefix_str::*;
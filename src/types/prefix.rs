@@ -0,0 +1,222 @@
+use bytemuck::{Pod, Zeroable};
+
+/// Trait to represent the length prefix of variable-size types.
+///
+/// The prefix is stored as part of the type's byte representation and
+/// indicates how many elements are in use.
+pub trait Prefix: Pod {
+    /// Maximum number of elements that the prefix can represent.
+    const MAX_CAPACITY: usize;
+
+    /// Returns the value of the prefix as a `usize`.
+    fn as_usize(&self) -> usize;
+
+    /// Increments the value of the prefix by one.
+    ///
+    /// Returns `false` if the value would overflow, in which case the
+    /// prefix is not modified.
+    fn checked_increment(&mut self) -> bool;
+
+    /// Decrements the value of the prefix by one.
+    ///
+    /// Returns `false` if the value would underflow, in which case the
+    /// prefix is not modified.
+    fn checked_decrement(&mut self) -> bool;
+
+    /// Sets the value of the prefix.
+    ///
+    /// The default implementation steps the prefix one element at a time, so
+    /// implementors should override it with a direct assignment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value cannot be represented by the prefix.
+    fn set_usize(&mut self, value: usize) {
+        while self.as_usize() < value {
+            assert!(
+                self.checked_increment(),
+                "value out of range for the prefix"
+            );
+        }
+        while self.as_usize() > value {
+            self.checked_decrement();
+        }
+    }
+
+    /// Returns the prefix with its bytes in the order used by prefixed strs.
+    ///
+    /// Prefixed strs store the standard prefix types as little-endian, while
+    /// big-endian prefixes keep their byte order. The default implementation
+    /// returns the prefix unchanged.
+    fn to_str_order(self) -> Self {
+        self
+    }
+}
+
+/// Macro to implement the `Prefix` trait for unsigned integer types.
+macro_rules! prefix_impl {
+    ( $($prefix_type:tt),+ ) => {
+        $(
+            impl Prefix for $prefix_type {
+                const MAX_CAPACITY: usize = if $prefix_type::MAX as u128 > usize::MAX as u128 {
+                    usize::MAX
+                } else {
+                    $prefix_type::MAX as usize
+                };
+
+                #[inline(always)]
+                fn as_usize(&self) -> usize {
+                    *self as usize
+                }
+
+                #[inline(always)]
+                fn checked_increment(&mut self) -> bool {
+                    if let Some(value) = self.checked_add(1) {
+                        *self = value;
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                #[inline(always)]
+                fn checked_decrement(&mut self) -> bool {
+                    if let Some(value) = self.checked_sub(1) {
+                        *self = value;
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                #[inline(always)]
+                fn set_usize(&mut self, value: usize) {
+                    *self = value.try_into().expect("value out of range for the prefix");
+                }
+
+                #[inline(always)]
+                fn to_str_order(self) -> Self {
+                    self.to_le()
+                }
+            }
+        )+
+    };
+}
+
+prefix_impl!(u8, u16, u32, u64);
+
+/// A length prefix stored as a big-endian integer.
+///
+/// The standard prefix types are read in the native byte order of the platform;
+/// this wrapper decodes the value as big-endian, so the data can be read the same
+/// way on any platform.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct BePrefix<T>(T);
+
+unsafe impl<T: Pod> Pod for BePrefix<T> {}
+
+unsafe impl<T: Zeroable> Zeroable for BePrefix<T> {}
+
+/// Macro to implement the `Prefix` trait for big-endian prefix types.
+macro_rules! be_prefix_impl {
+    ( $($prefix_type:tt),+ ) => {
+        $(
+            impl BePrefix<$prefix_type> {
+                /// Creates a prefix with the specified value.
+                pub fn new(value: $prefix_type) -> Self {
+                    Self(value.to_be())
+                }
+
+                /// Returns the decoded value of the prefix.
+                #[inline(always)]
+                pub fn get(&self) -> $prefix_type {
+                    <$prefix_type>::from_be(self.0)
+                }
+            }
+
+            impl Prefix for BePrefix<$prefix_type> {
+                const MAX_CAPACITY: usize = <$prefix_type as Prefix>::MAX_CAPACITY;
+
+                #[inline(always)]
+                fn as_usize(&self) -> usize {
+                    self.get() as usize
+                }
+
+                #[inline(always)]
+                fn checked_increment(&mut self) -> bool {
+                    let mut value = self.get();
+                    let result = value.checked_increment();
+                    *self = Self::new(value);
+                    result
+                }
+
+                #[inline(always)]
+                fn checked_decrement(&mut self) -> bool {
+                    let mut value = self.get();
+                    let result = value.checked_decrement();
+                    *self = Self::new(value);
+                    result
+                }
+
+                #[inline(always)]
+                fn set_usize(&mut self, value: usize) {
+                    let mut decoded = self.get();
+                    decoded.set_usize(value);
+                    *self = Self::new(decoded);
+                }
+
+                #[inline(always)]
+                fn to_str_order(self) -> Self {
+                    self
+                }
+            }
+        )+
+    };
+}
+
+be_prefix_impl!(u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::{Pod, Zeroable};
+
+    use crate::types::Prefix;
+
+    /// Prefix that only implements the required methods.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    struct Counter(u8);
+
+    impl Prefix for Counter {
+        const MAX_CAPACITY: usize = u8::MAX as usize;
+
+        fn as_usize(&self) -> usize {
+            self.0 as usize
+        }
+
+        fn checked_increment(&mut self) -> bool {
+            self.0.checked_increment()
+        }
+
+        fn checked_decrement(&mut self) -> bool {
+            self.0.checked_decrement()
+        }
+    }
+
+    #[test]
+    fn test_default_set_usize() {
+        let mut prefix = Counter(5);
+
+        prefix.set_usize(200);
+        assert_eq!(prefix.as_usize(), 200);
+        prefix.set_usize(0);
+        assert_eq!(prefix.as_usize(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "value out of range for the prefix")]
+    fn test_default_set_usize_out_of_range() {
+        Counter(0).set_usize(256);
+    }
+}
@@ -1,16 +1,23 @@
-use std::ops::{Deref, DerefMut};
-use std::str::{from_utf8_unchecked, from_utf8_unchecked_mut};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::str::{from_utf8_unchecked, from_utf8_unchecked_mut};
+
+use crate::{types::Prefix, TranslationError};
 
 macro_rules! prefix_str {
-    ( ($n:tt, $p:tt), $(($name:tt, $prefix:tt)),+ ) => {
-        prefix_str!(($n, $p));
-        prefix_str!($( ($name, $prefix) ),+);
+    ( ($n:tt, $p:tt, $d:tt, $e:tt), $(($name:tt, $prefix:tt, $decode:tt, $encode:tt)),+ ) => {
+        prefix_str!(($n, $p, $d, $e));
+        prefix_str!($( ($name, $prefix, $decode, $encode) ),+);
     };
-    ( ($name:tt, $prefix_type:tt) ) => {
+    ( ($name:tt, $prefix_type:tt, $decode:tt, $encode:tt) ) => {
         /// A "wrapped-pod" str with a prefix length.
         ///
         /// This is a wrapper around a byte slice that contains a length prefix, which
         /// enables having a str of variable size.
+        ///
+        /// The type is `Copy`, so a validated instance can be reused without having to
+        /// check the bytes again.
+        #[derive(Clone, Copy)]
         pub struct $name<'a> {
             /// The bytes representing the str.
             value: &'a [u8],
@@ -19,42 +26,86 @@ macro_rules! prefix_str {
         impl<'a> $name<'a> {
             /// Loads from a byte slice. This also checks that the resulting str bytes are valid UTF-8.
             /// To skip this safety check, see [`Self::from_bytes_unchecked`].
-            pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, std::str::Utf8Error> {
+            ///
+            /// Since the check is O(n), prefer copying a loaded instance or using
+            /// [`Self::validate`] over loading the same bytes repeatedly.
+            pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, core::str::Utf8Error> {
                 // Technically we are doing the check after this method, but it is being done before anyone can touch Self
                 let to_return = unsafe { Self::from_bytes_unchecked(bytes) };
-                std::str::from_utf8(to_return.value)?;
+                core::str::from_utf8(to_return.value)?;
                 Ok(to_return)
             }
 
+            /// Loads from a byte slice, returning the str and the number of bytes it occupies.
+            ///
+            /// The size includes the length prefix, so it can be used to advance a cursor
+            /// when parsing consecutive prefixed strs. Any bytes after the str are ignored.
+            pub fn from_bytes_with_size(
+                bytes: &'a [u8],
+            ) -> Result<(Self, usize), core::str::Utf8Error> {
+                let to_return = Self::from_bytes(bytes)?;
+                let size = to_return.size();
+                Ok((to_return, size))
+            }
+
+            /// Validates the str bytes once, so they can be loaded any number of times with
+            /// [`Self::from_bytes_trusted`] without checking them again.
+            pub fn validate(bytes: &'a [u8]) -> Result<Validated<'a, Self>, core::str::Utf8Error> {
+                Self::from_bytes(bytes)?;
+                Ok(Validated {
+                    bytes,
+                    _marker: PhantomData,
+                })
+            }
+
+            /// Loads from bytes that were already validated by [`Self::validate`].
+            ///
+            /// This skips the UTF-8 check, so it runs in O(1).
+            pub fn from_bytes_trusted(validated: Validated<'a, Self>) -> Self {
+                // safety: the str bytes were checked when `validated` was created
+                unsafe { Self::from_bytes_unchecked(validated.bytes) }
+            }
+
             /// Loads from a byte slice.
             ///
             /// # Safety
             /// The caller must guarantee that the resulting str bytes are valid UTF-8.
             pub unsafe fn from_bytes_unchecked(bytes: &'a [u8]) -> Self {
-                let (length, value) = bytes.split_at(std::mem::size_of::<$prefix_type>());
+                let (length, value) = bytes.split_at(core::mem::size_of::<$prefix_type>());
 
-                let length = bytemuck::pod_read_unaligned::<$prefix_type>(length);
+                let length = <$prefix_type>::$decode(length.try_into().unwrap());
                 let value = bytemuck::cast_slice(&value[..length as usize]);
 
                 Self { value }
             }
+
+            /// Returns the bytes of the str.
+            #[inline(always)]
+            fn bytes(&self) -> &[u8] {
+                self.value
+            }
         }
     };
 }
 
 macro_rules! prefix_str_mut {
-    ( ($n:tt, $p:tt), $(($name:tt, $prefix:tt)),+ ) => {
-        prefix_str_mut!(($n, $p));
-        prefix_str_mut!($( ($name, $prefix) ),+);
+    ( ($n:tt, $p:tt, $d:tt, $e:tt), $(($name:tt, $prefix:tt, $decode:tt, $encode:tt)),+ ) => {
+        prefix_str_mut!(($n, $p, $d, $e));
+        prefix_str_mut!($( ($name, $prefix, $decode, $encode) ),+);
     };
-    ( ($name:tt, $prefix_type:tt) ) => {
+    ( ($name:tt, $prefix_type:tt, $decode:tt, $encode:tt) ) => {
         /// A mutable "wrapped-pod" str with a prefix length.
         ///
         /// This is a wrapper around a byte slice that contains a length prefix, which
-        /// enables having a str of variable size.
+        /// enables having a str of variable size. The str can grow up to the length of
+        /// the byte slice.
         pub struct $name<'a> {
-            /// The bytes representing the str.
-            value: &'a mut [u8],
+            /// The bytes of the length prefix.
+            prefix: &'a mut [u8],
+
+            /// The bytes available to store the str, which are used up to the length
+            /// of the prefix.
+            data: &'a mut [u8],
         }
 
         impl<'a> $name<'a> {
@@ -62,10 +113,10 @@ macro_rules! prefix_str_mut {
             /// To skip this safety check, see [`Self::new_unchecked`].
             ///
             /// The `data` is used as the storage for the type.
-            pub fn new(data: &'a mut [u8]) -> Result<Self, std::str::Utf8Error> {
+            pub fn new(data: &'a mut [u8]) -> Result<Self, core::str::Utf8Error> {
                 // Check that the bytes are valid UTF-8
                 let to_return = unsafe { Self::new_unchecked(data) };
-                std::str::from_utf8(to_return.value)?;
+                core::str::from_utf8(to_return.bytes())?;
                 Ok(to_return)
             }
 
@@ -78,8 +129,8 @@ macro_rules! prefix_str_mut {
             /// The resulting bytes would be the `data` after the size of the length bytes and up to
             /// the value of the length bytes.
             pub unsafe fn new_unchecked(data: &'a mut [u8]) -> Self {
-                let type_length = std::mem::size_of::<$prefix_type>();
-                let length = (data.len().saturating_sub(type_length) as $prefix_type).to_le_bytes();
+                let type_length = core::mem::size_of::<$prefix_type>();
+                let length = (data.len().saturating_sub(type_length) as $prefix_type).$encode();
                 data[..type_length].copy_from_slice(&length);
                 Self::from_bytes_mut(data)
             }
@@ -91,12 +142,44 @@ macro_rules! prefix_str_mut {
             /// The resulting bytes would be the `data` after the size of the length bytes and up to
             /// the value of the length bytes.
             pub unsafe fn from_bytes_mut(bytes: &'a mut [u8]) -> Self {
-                let (length, value) = bytes.split_at_mut(std::mem::size_of::<$prefix_type>());
+                let (prefix, data) = bytes.split_at_mut(core::mem::size_of::<$prefix_type>());
+                let to_return = Self { prefix, data };
+                // validates that the length is within the bounds of the data
+                let _ = &to_return.data[..to_return.value_len()];
+
+                to_return
+            }
 
-                let length = bytemuck::pod_read_unaligned::<$prefix_type>(length);
-                let value = bytemuck::cast_slice_mut(&mut value[..length as usize]);
+            /// Returns the length of the str stored in the prefix.
+            #[inline(always)]
+            fn value_len(&self) -> usize {
+                <$prefix_type>::$decode((&*self.prefix).try_into().unwrap()) as usize
+            }
 
-                Self { value }
+            /// Returns the bytes of the str.
+            #[inline(always)]
+            fn bytes(&self) -> &[u8] {
+                &self.data[..self.value_len()]
+            }
+
+            /// Appends a `&str` to the end of the prefixed str, increasing its length.
+            ///
+            /// Returns [`TranslationError::CapacityExceeded`] if the resulting str does not fit
+            /// in the byte slice or its length cannot be represented by the prefix; in this
+            /// case, the str is not modified.
+            pub fn push_str(&mut self, string: &str) -> Result<(), TranslationError> {
+                let start = self.value_len();
+                let end = start
+                    .checked_add(string.len())
+                    .filter(|end| *end <= self.data.len())
+                    .ok_or(TranslationError::CapacityExceeded)?;
+                let length: $prefix_type =
+                    end.try_into().map_err(|_| TranslationError::CapacityExceeded)?;
+
+                self.data[start..end].copy_from_slice(string.as_bytes());
+                self.prefix.copy_from_slice(&length.$encode());
+
+                Ok(())
             }
 
             /// Copy the content of a slice into the prefixed str.
@@ -105,9 +188,11 @@ macro_rules! prefix_str_mut {
             /// The caller must guarantee that the `slice` from the start to `min(self.len(), slice.len())`
             /// is valid UTF-8.
             pub unsafe fn copy_from_slice(&mut self, slice: &[u8]) {
-                let length = std::cmp::min(self.value.len(), slice.len());
-                self.value[..length].clone_from_slice(&slice[..length]);
-                self.value[length..].fill(0);
+                let value_len = self.value_len();
+                let value = &mut self.data[..value_len];
+                let length = core::cmp::min(value.len(), slice.len());
+                value[..length].clone_from_slice(&slice[..length]);
+                value[length..].fill(0);
             }
 
             /// Copy the content of a `&str` into the prefixed str.
@@ -120,7 +205,8 @@ macro_rules! prefix_str_mut {
         impl<'a> DerefMut for $name<'a> {
             #[inline]
             fn deref_mut(&mut self) -> &mut str {
-                unsafe { from_utf8_unchecked_mut(self.value) }
+                let length = self.value_len();
+                unsafe { from_utf8_unchecked_mut(&mut self.data[..length]) }
             }
         }
     };
@@ -144,7 +230,7 @@ macro_rules! prefix_str_type {
             /// This is different than the `len` method of `str` because it includes the
             /// length of the prefix.
             pub fn size(&self) -> usize {
-                std::mem::size_of::<$prefix_type>() + self.value.len()
+                core::mem::size_of::<$prefix_type>() + self.bytes().len()
             }
         }
 
@@ -153,27 +239,141 @@ macro_rules! prefix_str_type {
 
             #[inline]
             fn deref(&self) -> &str {
-                unsafe { from_utf8_unchecked(self.value) }
+                unsafe { from_utf8_unchecked(self.bytes()) }
             }
         }
     };
 }
 
+/// Implements Borsh serialization for str types with a `u32` little-endian prefix.
+///
+/// The layout of these types matches the one Borsh uses for `String` values.
+#[cfg(feature = "borsh")]
+macro_rules! borsh_impl {
+    ( $($name:tt),+ ) => {
+        $(
+            impl<'a> borsh::BorshSerialize for $name<'a> {
+                fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+                    writer.write_all(&(self.bytes().len() as u32).to_le_bytes())?;
+                    writer.write_all(self.bytes())
+                }
+            }
+        )+
+    };
+}
+
+/// Bytes of a prefixed str of type `T` that were checked to be valid UTF-8.
+///
+/// This can only be created by the `validate` method of the read-only prefixed
+/// str types. Since it borrows the bytes immutably, they cannot change after the
+/// check, so `from_bytes_trusted` can load them without validating them again.
+#[derive(Clone, Copy)]
+pub struct Validated<'a, T> {
+    /// The validated bytes, including the length prefix.
+    bytes: &'a [u8],
+
+    _marker: PhantomData<T>,
+}
+
 // "read-only" impl
-prefix_str!((U8PrefixStr, u8), (U16PrefixStr, u16));
+prefix_str!(
+    (U8PrefixStr, u8, from_le_bytes, to_le_bytes),
+    (U16PrefixStr, u16, from_le_bytes, to_le_bytes),
+    (U16PrefixStrBe, u16, from_be_bytes, to_be_bytes),
+    (U32PrefixStr, u32, from_le_bytes, to_le_bytes)
+);
 // "mutable" impl
-prefix_str_mut!((U8PrefixStrMut, u8), (U16PrefixStrMut, u16));
+prefix_str_mut!(
+    (U8PrefixStrMut, u8, from_le_bytes, to_le_bytes),
+    (U16PrefixStrMut, u16, from_le_bytes, to_le_bytes),
+    (U16PrefixStrBeMut, u16, from_be_bytes, to_be_bytes),
+    (U32PrefixStrMut, u32, from_le_bytes, to_le_bytes)
+);
 // "shared" impl
 prefix_str_type!(
     (U8PrefixStr, u8),
     (U8PrefixStrMut, u8),
     (U16PrefixStr, u16),
-    (U16PrefixStrMut, u16)
+    (U16PrefixStrMut, u16),
+    (U16PrefixStrBe, u16),
+    (U16PrefixStrBeMut, u16),
+    (U32PrefixStr, u32),
+    (U32PrefixStrMut, u32)
 );
+// "borsh" impl
+#[cfg(feature = "borsh")]
+borsh_impl!(U32PrefixStr, U32PrefixStrMut);
+
+#[cfg(feature = "borsh")]
+impl<'a> U32PrefixStr<'a> {
+    /// Loads a str serialized by Borsh from the start of the buffer, advancing the
+    /// buffer past it.
+    ///
+    /// This is the zero-copy counterpart of deserializing a `String`, where the str
+    /// borrows the buffer. `BorshDeserialize` is not implemented, since it can only
+    /// produce values that do not borrow from the input.
+    pub fn deserialize_borsh(buf: &mut &'a [u8]) -> borsh::io::Result<Self> {
+        use borsh::io::ErrorKind;
+
+        let length = buf
+            .get(..core::mem::size_of::<u32>())
+            .ok_or(ErrorKind::UnexpectedEof)?;
+        let size = (u32::from_le_bytes(length.try_into().unwrap()) as usize)
+            .saturating_add(core::mem::size_of::<u32>());
+
+        if buf.len() < size {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+
+        let (bytes, rest) = buf.split_at(size);
+        let value = Self::from_bytes(bytes).map_err(|_| ErrorKind::InvalidData)?;
+        *buf = rest;
+
+        Ok(value)
+    }
+}
+
+/// Writes a `&str` with a length prefix at the specified offset of the bytes.
+///
+/// Integer prefixes are written as little-endian and big-endian prefixes keep their
+/// byte order, so a `u16` prefix matches [`U16PrefixStr`] and a `BePrefix<u16>`
+/// prefix matches [`U16PrefixStrBe`]. Returns the offset just
+/// past the written str, which can be used to write the next value, or
+/// [`TranslationError::CapacityExceeded`] if the str does not fit in the bytes or its
+/// length cannot be represented by the prefix; in this case, the bytes are not modified.
+pub fn write_prefix_str<P: Prefix>(
+    dest: &mut [u8],
+    offset: usize,
+    string: &str,
+) -> Result<usize, TranslationError> {
+    if string.len() > P::MAX_CAPACITY {
+        return Err(TranslationError::CapacityExceeded);
+    }
+
+    let start = offset
+        .checked_add(core::mem::size_of::<P>())
+        .ok_or(TranslationError::CapacityExceeded)?;
+    let end = start
+        .checked_add(string.len())
+        .filter(|end| *end <= dest.len())
+        .ok_or(TranslationError::CapacityExceeded)?;
+
+    let mut prefix = P::zeroed();
+    prefix.set_usize(string.len());
+
+    dest[offset..start].copy_from_slice(bytemuck::bytes_of(&prefix.to_str_order()));
+    dest[start..end].copy_from_slice(string.as_bytes());
+
+    Ok(end)
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::types::{U16PrefixStr, U16PrefixStrMut, U8PrefixStr, U8PrefixStrMut};
+    use crate::types::{
+        write_prefix_str, BePrefix, U16PrefixStr, U16PrefixStrBe, U16PrefixStrBeMut,
+        U16PrefixStrMut, U32PrefixStr, U32PrefixStrMut, U8PrefixStr, U8PrefixStrMut,
+    };
+    use crate::TranslationError;
     use std::str::Utf8Error;
 
     type Result = std::result::Result<(), Utf8Error>;
@@ -269,4 +469,268 @@ mod tests {
         assert_eq!(prefix_str.as_str(), "str");
         Ok(())
     }
+
+    #[test]
+    fn test_big_endian() -> Result {
+        let mut data = [0u8; 5];
+        data[..2].copy_from_slice(&3u16.to_be_bytes());
+        data[2..].copy_from_slice("str".as_bytes());
+
+        let prefix_str = U16PrefixStrBe::from_bytes(&data)?;
+        assert_eq!(prefix_str.as_str(), "str");
+
+        let mut data = [0u8; 5];
+        let mut prefix_str = U16PrefixStrBeMut::new(&mut data)?;
+        prefix_str.copy_from_str("str");
+
+        assert_eq!(prefix_str.as_str(), "str");
+        assert_eq!(data[..2], 3u16.to_be_bytes());
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_validated() -> Result {
+        let mut data = [0u8; 4];
+        data[0] = 3;
+        data[1..].copy_from_slice("str".as_bytes());
+
+        // the bytes are validated once
+        let prefix_str = U8PrefixStr::from_bytes(&data)?;
+
+        for _ in 0..3 {
+            // copies are views over the same validated bytes, so they neither
+            // re-read nor re-validate the buffer
+            let copy = prefix_str;
+            assert_eq!(copy.as_ptr(), data[1..].as_ptr());
+            assert_eq!(copy.len(), 3);
+            assert_eq!(copy.size(), data.len());
+        }
+
+        assert_eq!(prefix_str.as_str(), "str");
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_bytes_trusted() -> Result {
+        let mut data = [0u8; 6];
+        data[..2].copy_from_slice(&4u16.to_be_bytes());
+        data[2..].copy_from_slice("abcd".as_bytes());
+
+        // the bytes are validated once and loaded repeatedly
+        let validated = U16PrefixStrBe::validate(&data)?;
+
+        for _ in 0..3 {
+            let prefix_str = U16PrefixStrBe::from_bytes_trusted(validated);
+            assert_eq!(prefix_str.as_str(), "abcd");
+            assert_eq!(prefix_str.as_ptr(), data[2..].as_ptr());
+        }
+
+        // invalid UTF-8 bytes are rejected when validating
+        data[2] = 0xff;
+        assert!(U16PrefixStrBe::validate(&data).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_u32_prefix() -> Result {
+        let mut data = [0u8; 7];
+        let mut prefix_str = U32PrefixStrMut::new(&mut data)?;
+        prefix_str.copy_from_str("str");
+
+        assert_eq!(prefix_str.as_str(), "str");
+        assert_eq!(prefix_str.size(), data.len());
+
+        let prefix_str = U32PrefixStr::from_bytes(&data)?;
+        assert_eq!(prefix_str.as_str(), "str");
+        assert_eq!(data[..4], 3u32.to_le_bytes());
+        Ok(())
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh() -> Result {
+        let expected = borsh::to_vec(&String::from("str")).unwrap();
+
+        let mut data = [0u8; 7];
+        let mut prefix_str = U32PrefixStrMut::new(&mut data)?;
+        prefix_str.copy_from_str("str");
+        assert_eq!(borsh::to_vec(&prefix_str).unwrap(), expected);
+
+        // the layout is the same as the borsh string
+        assert_eq!(data.as_slice(), expected.as_slice());
+
+        let prefix_str = U32PrefixStr::from_bytes(&expected)?;
+        assert_eq!(prefix_str.as_str(), "str");
+        assert_eq!(borsh::to_vec(&prefix_str).unwrap(), expected);
+        Ok(())
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_deserialize_borsh() {
+        let values = (String::from("hello"), String::from("stevia"));
+        let data = borsh::to_vec(&values).unwrap();
+
+        let mut buf = data.as_slice();
+        let first = U32PrefixStr::deserialize_borsh(&mut buf).unwrap();
+        let second = U32PrefixStr::deserialize_borsh(&mut buf).unwrap();
+        assert_eq!(first.as_str(), "hello");
+        assert_eq!(second.as_str(), "stevia");
+        assert!(buf.is_empty());
+
+        // round-trips back to the borsh values
+        let serialized = [
+            borsh::to_vec(&first).unwrap(),
+            borsh::to_vec(&second).unwrap(),
+        ];
+        assert_eq!(serialized.concat(), data);
+        assert_eq!(
+            borsh::from_slice::<String>(&serialized[1]).unwrap(),
+            values.1
+        );
+
+        // missing bytes and invalid UTF-8 are errors, leaving the buffer unchanged
+        let mut buf = &data[..8];
+        assert!(U32PrefixStr::deserialize_borsh(&mut buf).is_err());
+        assert!(U32PrefixStr::deserialize_borsh(&mut &data[..2]).is_err());
+        assert_eq!(buf.len(), 8);
+
+        let mut invalid = data.clone();
+        invalid[4] = 0xff;
+        assert!(U32PrefixStr::deserialize_borsh(&mut invalid.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_starts_ends_with() -> Result {
+        let mut data = [0u8; 10];
+        let mut prefix_str = U8PrefixStrMut::new(&mut data)?;
+        prefix_str.copy_from_str("prefix");
+
+        let prefix_str = U8PrefixStr::from_bytes(&data)?;
+        // str methods are available through deref and only consider the
+        // bytes up to the length
+        assert!(prefix_str.starts_with("pre"));
+        assert!(prefix_str.ends_with("fix\0\0\0"));
+        assert!(!prefix_str.ends_with("fix"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_str() -> Result {
+        let mut data = [0u8; 13];
+        // the zeroed bytes represent an empty str
+        let mut prefix_str = unsafe { U16PrefixStrMut::from_bytes_mut(&mut data) };
+        assert_eq!(prefix_str.len(), 0);
+
+        prefix_str.push_str("hello").unwrap();
+        prefix_str.push_str(", ").unwrap();
+        prefix_str.push_str("you").unwrap();
+        assert_eq!(prefix_str.as_str(), "hello, you");
+
+        // does not fit in the remaining bytes
+        assert_eq!(
+            prefix_str.push_str("!!"),
+            Err(TranslationError::CapacityExceeded)
+        );
+        prefix_str.push_str("!").unwrap();
+        assert_eq!(prefix_str.size(), 13);
+
+        let prefix_str = U16PrefixStr::from_bytes(&data)?;
+        assert_eq!(prefix_str.as_str(), "hello, you!");
+        assert_eq!(data[..2], 11u16.to_le_bytes());
+
+        // the length does not fit in the prefix
+        let mut data = [0u8; 300];
+        let mut prefix_str = unsafe { U8PrefixStrMut::from_bytes_mut(&mut data) };
+        prefix_str.push_str(&"a".repeat(255)).unwrap();
+        assert_eq!(
+            prefix_str.push_str("a"),
+            Err(TranslationError::CapacityExceeded)
+        );
+        assert_eq!(prefix_str.len(), 255);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_bytes_with_size() -> Result {
+        let mut data = [0u8; 13];
+        data[..2].copy_from_slice(&5u16.to_le_bytes());
+        data[2..7].copy_from_slice(b"hello");
+        data[7..9].copy_from_slice(&3u16.to_le_bytes());
+        data[9..12].copy_from_slice(b"you");
+
+        let mut cursor = 0;
+        let (first, size) = U16PrefixStr::from_bytes_with_size(&data[cursor..])?;
+        assert_eq!(first.as_str(), "hello");
+        assert_eq!(size, 7);
+        cursor += size;
+
+        let (second, size) = U16PrefixStr::from_bytes_with_size(&data[cursor..])?;
+        assert_eq!(second.as_str(), "you");
+        assert_eq!(size, 5);
+        cursor += size;
+
+        // trailing byte
+        assert_eq!(cursor, data.len() - 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_prefix_str() -> Result {
+        let mut data = [0u8; 13];
+
+        let offset = write_prefix_str::<u8>(&mut data, 0, "hello").unwrap();
+        assert_eq!(offset, 6);
+        let offset = write_prefix_str::<u8>(&mut data, offset, "world").unwrap();
+        assert_eq!(offset, 12);
+
+        let (first, size) = U8PrefixStr::from_bytes_with_size(&data)?;
+        assert_eq!(first.as_str(), "hello");
+        let second = U8PrefixStr::from_bytes(&data[size..])?;
+        assert_eq!(second.as_str(), "world");
+
+        // big-endian prefix
+        let mut data = [0u8; 10];
+        let offset = write_prefix_str::<BePrefix<u16>>(&mut data, 0, "abc").unwrap();
+        assert_eq!(offset, 5);
+        assert_eq!(
+            write_prefix_str::<BePrefix<u16>>(&mut data, offset, "de"),
+            Ok(9)
+        );
+
+        let (first, size) = U16PrefixStrBe::from_bytes_with_size(&data)?;
+        assert_eq!(first.as_str(), "abc");
+        assert_eq!(U16PrefixStrBe::from_bytes(&data[size..])?.as_str(), "de");
+
+        // does not fit in the remaining bytes, leaving them unchanged
+        assert_eq!(
+            write_prefix_str::<BePrefix<u16>>(&mut data, 9, "f"),
+            Err(TranslationError::CapacityExceeded)
+        );
+        assert_eq!(
+            write_prefix_str::<u8>(&mut data, usize::MAX, ""),
+            Err(TranslationError::CapacityExceeded)
+        );
+        assert_eq!(&data[9..], &[0]);
+
+        // little-endian prefixes
+        let mut data = [0u8; 12];
+        let offset = write_prefix_str::<u16>(&mut data, 0, "abc").unwrap();
+        assert_eq!(data[..2], 3u16.to_le_bytes());
+        assert_eq!(write_prefix_str::<u32>(&mut data, offset, "de"), Ok(11));
+
+        let (first, size) = U16PrefixStr::from_bytes_with_size(&data)?;
+        assert_eq!(first.as_str(), "abc");
+        assert_eq!(U32PrefixStr::from_bytes(&data[size..])?.as_str(), "de");
+
+        // length cannot be represented by the prefix
+        let long = "a".repeat(256);
+        let mut data = [0u8; 300];
+        assert_eq!(
+            write_prefix_str::<u8>(&mut data, 0, &long),
+            Err(TranslationError::CapacityExceeded)
+        );
+        assert_eq!(write_prefix_str::<u16>(&mut data, 0, &long), Ok(258));
+        Ok(())
+    }
 }